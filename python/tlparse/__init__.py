@@ -0,0 +1,31 @@
+"""Parse TORCH_LOG logs produced by PyTorch torch.compile.
+
+>>> import tlparse
+>>> result = tlparse.parse("dedicated_log_torch_trace_rank_0.log")
+>>> pandas.DataFrame(result["metrics"])
+"""
+
+import sys
+
+from ._tlparse import (
+    __version__,
+    analyze_execution_order,
+    analyze_graph_runtime_deltas,
+    parse,
+    parse_graph_execution_order,
+)
+from ._tlparse import main as _main
+
+__all__ = [
+    "__version__",
+    "analyze_execution_order",
+    "analyze_graph_runtime_deltas",
+    "main",
+    "parse",
+    "parse_graph_execution_order",
+]
+
+
+def main():
+    """Entry point for the ``tlparse`` console script."""
+    sys.exit(_main(sys.argv))
@@ -1,12 +1,21 @@
-use clap::Parser;
+//! The `tlparse` command line, shared by the binary and the Python package's entry point.
+
+use clap::{Parser, Subcommand};
 
 use anyhow::{bail, Context};
+use std::ffi::OsString;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use tlparse::{
+use crate::config::{Templates, TlparseConfig, CONFIG_FILE_NAME};
+use crate::parsers::StructuredLogParser;
+use crate::serve::{LazyReport, Server};
+use crate::templates::overridable_templates;
+use crate::{
+    bundle_single_file,
     // New reusable library API for multi-rank landing generation
     generate_multi_rank_landing,
+    markdown_summary,
     parse_path,
     // Context used to pass rank list; other fields are recomputed inside the API
     MultiRankContext,
@@ -16,8 +25,12 @@ use tlparse::{
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 #[command(propagate_version = true)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Cli {
-    path: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(required = true)]
+    path: Option<PathBuf>,
     /// Parse most recent log
     #[arg(long)]
     latest: bool,
@@ -57,18 +70,300 @@ pub struct Cli {
     /// Parse all ranks and create a unified multi-rank report
     #[arg(long)]
     all_ranks_html: bool,
+    /// Store payload dumps once under payloads/<md5> and reference them from every compile id
+    /// that logged them, instead of writing a copy per compile id
+    #[arg(long)]
+    content_addressed: bool,
+    /// Write the report as one self-contained HTML file (the output path with an .html
+    /// extension) instead of a directory, for attaching to tickets or sending in chat
+    #[arg(long)]
+    single_file: bool,
+    /// Load parser plugins (shared libraries exporting tlparse_plugin_v1) from this directory
+    #[arg(long)]
+    plugin_dir: Option<PathBuf>,
+    /// Artifact rendering rules, defaults to `tlparse.toml` in the current directory if it exists
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Override built-in templates and stylesheets with the files in this directory (see
+    /// `tlparse templates`)
+    #[arg(long)]
+    template_dir: Option<PathBuf>,
+    /// Only run these parsers (comma separated names, see `tlparse parsers`)
+    #[arg(long, value_delimiter = ',')]
+    only_parsers: Vec<String>,
+    /// Don't run these parsers; their envelopes are written out as raw payloads instead
+    #[arg(long, value_delimiter = ',')]
+    skip_parsers: Vec<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the registered parsers and the envelope keys they consume
+    Parsers {
+        /// Also list parser plugins from this directory
+        #[arg(long)]
+        plugin_dir: Option<PathBuf>,
+        /// Also list rendering rules from this config file
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+    /// Serve the report of a log on localhost, rendering payloads and highlighted code when
+    /// they're first requested instead of up front
+    Serve {
+        path: PathBuf,
+        /// Port to listen on, 0 picks a free one
+        #[arg(long, default_value_t = 8000)]
+        port: u16,
+        /// Don't open browser once the server is up
+        #[arg(long)]
+        no_browser: bool,
+        /// Load parser plugins (shared libraries exporting tlparse_plugin_v1) from this directory
+        #[arg(long)]
+        plugin_dir: Option<PathBuf>,
+        /// Artifact rendering rules, defaults to `tlparse.toml` in the current directory if it
+        /// exists
+        #[arg(long)]
+        config: Option<PathBuf>,
+        /// Override built-in templates and stylesheets with the files in this directory
+        #[arg(long)]
+        template_dir: Option<PathBuf>,
+        /// For inductor provenance tracking highlighter
+        #[arg(short, long)]
+        inductor_provenance: bool,
+    },
+    /// Print a short Markdown summary of a log to stdout: compile ids and their status, the
+    /// slowest compiles, graph breaks, recompiles, cache hit rates and parse stats
+    Summary {
+        path: PathBuf,
+        /// Load parser plugins (shared libraries exporting tlparse_plugin_v1) from this directory
+        #[arg(long)]
+        plugin_dir: Option<PathBuf>,
+        /// Artifact rendering rules, defaults to `tlparse.toml` in the current directory if it
+        /// exists
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+    /// Browse the report of a log in the terminal: the stack trie, compile ids colored by
+    /// status, and their artifacts in a pager
+    #[cfg(feature = "tui")]
+    Tui {
+        path: PathBuf,
+        /// Load parser plugins (shared libraries exporting tlparse_plugin_v1) from this directory
+        #[arg(long)]
+        plugin_dir: Option<PathBuf>,
+        /// Artifact rendering rules, defaults to `tlparse.toml` in the current directory if it
+        /// exists
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+    /// List the templates and stylesheets --template-dir can override, and the context each
+    /// template is rendered with
+    Templates {
+        /// Write the built-in versions to this directory, as a starting point for overrides
+        #[arg(long)]
+        dump: Option<PathBuf>,
+    },
+}
+
+fn load_templates(template_dir: Option<&PathBuf>) -> anyhow::Result<Templates> {
+    match template_dir {
+        Some(dir) => Templates::load(dir),
+        None => Ok(Templates::default()),
+    }
+}
+
+fn handle_templates(dump: Option<&PathBuf>) -> anyhow::Result<()> {
+    if let Some(dir) = dump {
+        fs::create_dir_all(dir)?;
+    }
+    for (name, context, template) in overridable_templates() {
+        if context.is_empty() {
+            println!("{name}: stylesheet");
+        } else {
+            println!("{name}: {context}");
+        }
+        if let Some(dir) = dump {
+            fs::write(dir.join(name), template)?;
+        }
+    }
+    Ok(())
+}
+
+/// Load --plugin-dir plugins and the tlparse.toml config
+fn load_extensions(
+    plugin_dir: Option<&PathBuf>,
+    config: Option<PathBuf>,
+) -> anyhow::Result<(Vec<Box<dyn StructuredLogParser>>, TlparseConfig)> {
+    let custom_parsers = match plugin_dir {
+        Some(dir) => crate::plugins::load_plugins(dir)?,
+        None => Vec::new(),
+    };
+    let config_path =
+        config.or_else(|| Some(PathBuf::from(CONFIG_FILE_NAME)).filter(|p| p.is_file()));
+    let tlparse_config = match config_path {
+        Some(ref path) => TlparseConfig::load(path)?,
+        None => TlparseConfig::default(),
+    };
+    Ok((custom_parsers, tlparse_config))
+}
+
+fn handle_parsers(plugin_dir: Option<&PathBuf>, config: Option<PathBuf>) -> anyhow::Result<()> {
+    let (custom_parsers, tlparse_config) = load_extensions(plugin_dir, config)?;
+    let config = ParseConfig {
+        custom_parsers,
+        render_rules: tlparse_config.rules,
+        ..Default::default()
+    };
+    for (name, keys) in crate::registered_parsers(&config) {
+        let keys = if keys.is_empty() {
+            "(none)".to_string()
+        } else {
+            keys.join(", ")
+        };
+        println!("{name}: {keys}");
+    }
+    Ok(())
+}
+
+fn handle_serve(
+    path: &Path,
+    port: u16,
+    open_browser: bool,
+    plugin_dir: Option<&PathBuf>,
+    config: Option<PathBuf>,
+    template_dir: Option<&PathBuf>,
+    inductor_provenance: bool,
+) -> anyhow::Result<()> {
+    let (custom_parsers, tlparse_config) = load_extensions(plugin_dir, config)?;
+    let config = ParseConfig {
+        custom_parsers,
+        templates: load_templates(template_dir)?,
+        inductor_provenance,
+        render_rules: tlparse_config.rules,
+        only_parsers: tlparse_config.parsers.only,
+        skip_parsers: tlparse_config.parsers.skip,
+        ..Default::default()
+    };
+    let report = LazyReport::open(path, &config)?;
+    let server = Server::bind(report, port)?;
+    let url = server.url();
+    println!("Serving {} at {url}", path.display());
+    if open_browser {
+        // The server is still useful without a browser, e.g. over ssh with port forwarding
+        if let Err(err) = opener::open(&url) {
+            eprintln!("Couldn't open browser: {err}");
+        }
+    }
+    server.run()
+}
+
+fn handle_summary(
+    path: &Path,
+    plugin_dir: Option<&PathBuf>,
+    config: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let (custom_parsers, tlparse_config) = load_extensions(plugin_dir, config)?;
+    let config = ParseConfig {
+        custom_parsers,
+        render_rules: tlparse_config.rules,
+        only_parsers: tlparse_config.parsers.only,
+        skip_parsers: tlparse_config.parsers.skip,
+        ..Default::default()
+    };
+    let output = parse_path(&path.to_path_buf(), &config)?;
+    let title = path
+        .file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy();
+    print!("{}", markdown_summary(&output, &title)?);
+    Ok(())
 }
 
-fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
+#[cfg(feature = "tui")]
+fn handle_tui(
+    path: &Path,
+    plugin_dir: Option<&PathBuf>,
+    config: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let (custom_parsers, tlparse_config) = load_extensions(plugin_dir, config)?;
+    let config = ParseConfig {
+        custom_parsers,
+        render_rules: tlparse_config.rules,
+        only_parsers: tlparse_config.parsers.only,
+        skip_parsers: tlparse_config.parsers.skip,
+        ..Default::default()
+    };
+    let report = LazyReport::open(path, &config)?;
+    let title = path
+        .file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy();
+    crate::tui::run(crate::tui::App::new(report, &title)?)
+}
+
+/// Run the command line with `args`, including the program name.
+pub fn run<I, T>(args: I) -> anyhow::Result<()>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let cli = Cli::parse_from(args);
+
+    match cli.command {
+        Some(Command::Parsers { plugin_dir, config }) => {
+            return handle_parsers(plugin_dir.as_ref(), config);
+        }
+        Some(Command::Serve {
+            path,
+            port,
+            no_browser,
+            plugin_dir,
+            config,
+            template_dir,
+            inductor_provenance,
+        }) => {
+            return handle_serve(
+                &path,
+                port,
+                !no_browser,
+                plugin_dir.as_ref(),
+                config,
+                template_dir.as_ref(),
+                inductor_provenance,
+            );
+        }
+        Some(Command::Summary {
+            path,
+            plugin_dir,
+            config,
+        }) => {
+            return handle_summary(&path, plugin_dir.as_ref(), config);
+        }
+        #[cfg(feature = "tui")]
+        Some(Command::Tui {
+            path,
+            plugin_dir,
+            config,
+        }) => {
+            return handle_tui(&path, plugin_dir.as_ref(), config);
+        }
+        Some(Command::Templates { dump }) => {
+            return handle_templates(dump.as_ref());
+        }
+        None => {}
+    }
+    let cli_path = cli.path.context("Missing path")?;
 
     // Early validation of incompatible flags
     if cli.all_ranks_html && cli.latest {
         bail!("--latest cannot be used with --all-ranks-html");
     }
+    if cli.all_ranks_html && cli.single_file {
+        bail!("--single-file cannot be used with --all-ranks-html");
+    }
 
     let path = if cli.latest {
-        let input_path = cli.path;
+        let input_path = cli_path;
         // Path should be a directory
         if !input_path.is_dir() {
             bail!(
@@ -88,18 +383,34 @@ fn main() -> anyhow::Result<()> {
         };
         last_modified_file.path()
     } else {
-        cli.path
+        cli_path
     };
 
+    let (custom_parsers, tlparse_config) = load_extensions(cli.plugin_dir.as_ref(), cli.config)?;
+    let only_parsers = if cli.only_parsers.is_empty() {
+        tlparse_config.parsers.only
+    } else {
+        cli.only_parsers
+    };
+    let mut skip_parsers = tlparse_config.parsers.skip;
+    skip_parsers.extend(cli.skip_parsers);
+
     let config = ParseConfig {
         strict: cli.strict,
         strict_compile_id: cli.strict_compile_id,
-        custom_parsers: Vec::new(),
+        custom_parsers,
         custom_header_html: cli.custom_header_html,
         verbose: cli.verbose,
         plain_text: cli.plain_text,
         export: cli.export,
         inductor_provenance: cli.inductor_provenance,
+        render_rules: tlparse_config.rules,
+        only_parsers,
+        skip_parsers,
+        content_addressed: cli.content_addressed,
+        // Ranks dump mostly the same graphs and code, so they share one payloads/
+        shared_payloads: cli.all_ranks_html && cli.content_addressed,
+        templates: load_templates(cli.template_dir.as_ref())?,
     };
 
     if cli.all_ranks_html {
@@ -112,6 +423,7 @@ fn main() -> anyhow::Result<()> {
             cli.out,
             !cli.no_browser,
             cli.overwrite,
+            cli.single_file,
         )?;
     }
     Ok(())
@@ -126,9 +438,9 @@ fn setup_output_directory(out_path: &PathBuf, overwrite: bool) -> anyhow::Result
                 out_path.display()
             );
         }
-        fs::remove_dir_all(&out_path)?;
+        fs::remove_dir_all(out_path)?;
     }
-    fs::create_dir_all(&out_path)?;
+    fs::create_dir_all(out_path)?;
     Ok(())
 }
 
@@ -136,7 +448,7 @@ fn setup_output_directory(out_path: &PathBuf, overwrite: bool) -> anyhow::Result
 fn parse_and_write_output(
     config: &ParseConfig,
     log_path: &PathBuf,
-    output_dir: &PathBuf,
+    output_dir: &Path,
 ) -> anyhow::Result<PathBuf> {
     let output = parse_path(log_path, config)?;
 
@@ -150,6 +462,28 @@ fn parse_and_write_output(
     Ok(output_dir.join("index.html"))
 }
 
+/// Parse a log file and bundle the report into `out` with an .html extension.
+fn write_single_file(
+    config: &ParseConfig,
+    log_path: &Path,
+    out: &Path,
+    overwrite: bool,
+) -> anyhow::Result<PathBuf> {
+    let out_file = out.with_extension("html");
+    if out_file.exists() && !overwrite {
+        bail!(
+            "File {} already exists; pass --overwrite to replace it or use -o OUTFILE",
+            out_file.display()
+        );
+    }
+    let output = parse_path(&log_path.to_path_buf(), config)?;
+    let title = log_path
+        .file_name()
+        .map_or("tlparse".into(), |name| name.to_string_lossy());
+    fs::write(&out_file, bundle_single_file(&output, &title)?)?;
+    Ok(out_file)
+}
+
 fn handle_one_rank(
     cfg: &ParseConfig,
     input_path: PathBuf,
@@ -157,6 +491,7 @@ fn handle_one_rank(
     out_dir: PathBuf,
     open_browser: bool,
     overwrite: bool,
+    single_file: bool,
 ) -> anyhow::Result<()> {
     // Resolve which log file we should parse
     let log_path = if latest {
@@ -168,7 +503,7 @@ fn handle_one_rank(
         }
         std::fs::read_dir(input_path)?
             .flatten()
-            .filter(|e| e.metadata().ok().map_or(false, |m| m.is_file()))
+            .filter(|e| e.metadata().is_ok_and(|m| m.is_file()))
             .max_by_key(|e| e.metadata().unwrap().modified().unwrap())
             .map(|e| e.path())
             .context("No files found in directory for --latest")?
@@ -176,8 +511,12 @@ fn handle_one_rank(
         input_path.clone()
     };
 
-    setup_output_directory(&out_dir, overwrite)?;
-    let main_output_file = parse_and_write_output(cfg, &log_path, &out_dir)?;
+    let main_output_file = if single_file {
+        write_single_file(cfg, &log_path, &out_dir, overwrite)?
+    } else {
+        setup_output_directory(&out_dir, overwrite)?;
+        parse_and_write_output(cfg, &log_path, &out_dir)?
+    };
 
     if open_browser {
         opener::open(&main_output_file)?;
@@ -237,7 +576,7 @@ fn handle_all_ranks(
     for (log_path, rank_num) in rank_logs {
         let subdir = out_path.join(format!("rank_{rank_num}"));
         println!("Processing rank {rank_num} → {}", subdir.display());
-        handle_one_rank(cfg, log_path, false, subdir, false, overwrite)?;
+        handle_one_rank(cfg, log_path, false, subdir, false, overwrite, false)?;
     }
     // Build a minimal context; values other than ranks are recomputed inside the library API
     let ctx = MultiRankContext {
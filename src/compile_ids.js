@@ -0,0 +1,69 @@
+// Filtering, sorting and collapsing of the compile ids listed on the index page, by the data-*
+// attributes of each entry (see CompileIdSummary).  Entries are moved, not rebuilt, so links and
+// anchors into the list keep working.
+document.addEventListener('DOMContentLoaded', function () {
+    const list = document.getElementById('compile-ids');
+    const filter = document.getElementById('compile-id-filter');
+    const sort = document.getElementById('compile-id-sort');
+    const collapse = document.getElementById('compile-id-collapse');
+    const count = document.getElementById('compile-id-count');
+    if (!list || !filter) return;
+    // In log order
+    const entries = [...list.children];
+    const filters = {
+        '': () => true,
+        'failure': entry => entry.dataset.status === 'status-error',
+        'break': entry => entry.dataset.status === 'status-break',
+        'miss': entry => entry.dataset.cache === 'miss',
+    };
+
+    function number(entry, key) {
+        const value = parseFloat(entry.dataset[key]);
+        return isNaN(value) ? null : value;
+    }
+
+    // Entries without the value go last; sort is stable, so ties stay in log order
+    function compareBy(key, descending) {
+        return function (a, b) {
+            const x = number(a, key), y = number(b, key);
+            if (x === null || y === null) return (x === null) - (y === null);
+            return descending ? y - x : x - y;
+        };
+    }
+
+    function update() {
+        const sorted = entries.slice();
+        if (sort.value === 'compile-time') sorted.sort(compareBy('compileTime', true));
+        if (sort.value === 'start-time') sorted.sort(compareBy('startTime', false));
+        let shown = 0;
+        for (const entry of sorted) {
+            const visible = filters[filter.value](entry);
+            entry.style.display = visible ? '' : 'none';
+            shown += visible;
+            list.appendChild(entry);
+        }
+        count.textContent = shown + ' of ' + entries.length + ' compile ids';
+    }
+
+    collapse.addEventListener('change', function () {
+        for (const entry of entries) {
+            const nested = entry.querySelector('ul');
+            const marker = entry.querySelector('.marker');
+            if (nested) nested.style.display = collapse.checked ? 'none' : 'block';
+            if (marker) marker.classList.toggle('collapsed', collapse.checked);
+        }
+    });
+    filter.addEventListener('change', update);
+    sort.addEventListener('change', update);
+    // Following a link from the stack trie to a compile id the filter hides shows everything again
+    window.addEventListener('hashchange', function () {
+        const target = document.getElementById(decodeURIComponent(window.location.hash.slice(1)));
+        const entry = target && target.closest('.compile-id');
+        if (entry && entry.style.display === 'none') {
+            filter.value = '';
+            update();
+            target.scrollIntoView();
+        }
+    });
+    update();
+});
@@ -0,0 +1,203 @@
+//! `tlparse.toml`: declarative rendering rules for artifacts and envelope keys, so that log
+//! types which only need "pretty print this JSON" or "highlight this code" don't need a
+//! dedicated parser.
+//!
+//! ```toml
+//! [[rules]]
+//! artifact = "my_pass_stats"      # artifact name; a trailing * matches a prefix
+//! renderer = "table-from-json-array"
+//! display_name = "My pass stats"
+//! group = "My team"
+//!
+//! [[rules]]
+//! key = "my_log_type"             # envelope key tlparse doesn't know about
+//! renderer = "markdown"
+//!
+//! [[rules]]
+//! artifact = "noisy_debug_*"
+//! hidden = true                   # still written to disk, but not listed in the index
+//! ```
+//!
+//! The first rule matching an envelope wins. Rules without a renderer keep the default
+//! rendering and only change how the outputs are listed in the index.
+//!
+//! Parsers can also be enabled or disabled by name (see `tlparse parsers`), like
+//! `--only-parsers` / `--skip-parsers`:
+//!
+//! ```toml
+//! [parsers]
+//! skip = ["inductor_output_code"]
+//! ```
+
+use crate::parsers::{Envelope, Metadata};
+use crate::templates::overridable_templates;
+use anyhow::{bail, Context};
+use fxhash::FxHashMap;
+use serde::Deserialize;
+use std::path::Path;
+
+pub const CONFIG_FILE_NAME: &str = "tlparse.toml";
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Renderer {
+    Json,
+    PythonHighlight,
+    CppHighlight,
+    TableFromJsonArray,
+    Markdown,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RenderRule {
+    // Exactly one of artifact and key is set
+    pub artifact: Option<String>,
+    pub key: Option<String>,
+    pub renderer: Option<Renderer>,
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub hidden: bool,
+    pub group: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParserSelection {
+    #[serde(default)]
+    pub only: Vec<String>,
+    #[serde(default)]
+    pub skip: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TlparseConfig {
+    #[serde(default)]
+    pub rules: Vec<RenderRule>,
+    #[serde(default)]
+    pub parsers: ParserSelection,
+}
+
+impl TlparseConfig {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Couldn't read config {}", path.display()))?;
+        let config: TlparseConfig = toml::from_str(&contents)
+            .with_context(|| format!("Couldn't parse config {}", path.display()))?;
+        for (i, rule) in config.rules.iter().enumerate() {
+            if rule.artifact.is_some() == rule.key.is_some() {
+                bail!(
+                    "{}: rule {} must set exactly one of `artifact` and `key`",
+                    path.display(),
+                    i + 1
+                );
+            }
+        }
+        Ok(config)
+    }
+}
+
+fn pattern_matches(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => pattern == name,
+    }
+}
+
+impl RenderRule {
+    // The metadata this rule matches in an envelope, if any
+    pub fn matches<'e>(&self, e: &'e Envelope) -> Option<Metadata<'e>> {
+        if let Some(ref pattern) = self.artifact {
+            return e
+                .artifact
+                .as_ref()
+                .filter(|a| pattern_matches(pattern, &a.name))
+                .map(Metadata::Artifact);
+        }
+        let pattern = self.key.as_ref()?;
+        let mut keys: Vec<&String> = e
+            ._other
+            .keys()
+            .filter(|k| pattern_matches(pattern, k))
+            .collect();
+        keys.sort();
+        keys.first().and_then(|k| e.other(k))
+    }
+
+    // Whether this rule matches already extracted artifact or envelope key metadata
+    pub fn matches_metadata(&self, metadata: &Metadata) -> bool {
+        match (metadata, &self.artifact, &self.key) {
+            (Metadata::Artifact(a), Some(pattern), _) => pattern_matches(pattern, &a.name),
+            (Metadata::Other(key, _), _, Some(pattern)) => pattern_matches(pattern, key),
+            _ => false,
+        }
+    }
+
+    // Base name for files rendered by this rule
+    pub fn output_name(metadata: &Metadata) -> Option<String> {
+        match metadata {
+            Metadata::Artifact(a) => Some(a.name.clone()),
+            Metadata::Other(key, _) => Some(key.to_string()),
+            _ => None,
+        }
+    }
+}
+
+// The first rule matching an envelope, with the metadata it matched
+pub fn find_rule<'r, 'e>(
+    rules: &'r [RenderRule],
+    e: &'e Envelope,
+) -> Option<(&'r RenderRule, Metadata<'e>)> {
+    rules
+        .iter()
+        .find_map(|rule| rule.matches(e).map(|m| (rule, m)))
+}
+
+/// Templates and stylesheets overridden by `--template-dir`: a directory of files named after
+/// what they replace (see `tlparse templates`), e.g. `index.html` or `main.css`.
+#[derive(Debug, Default)]
+pub struct Templates {
+    // Overrides are used until the process exits, so they're leaked to fit the &'static str
+    // fields of the template contexts
+    overrides: FxHashMap<String, &'static str>,
+}
+
+impl Templates {
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let known = overridable_templates();
+        let mut overrides = FxHashMap::default();
+        let entries = std::fs::read_dir(dir)
+            .with_context(|| format!("Couldn't access template directory {}", dir.display()))?;
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') || !entry.path().is_file() {
+                continue;
+            }
+            if !known.iter().any(|(known_name, _, _)| *known_name == name) {
+                bail!(
+                    "{}: {name} doesn't override anything, see `tlparse templates` for the names",
+                    dir.display()
+                );
+            }
+            let contents = std::fs::read_to_string(entry.path())
+                .with_context(|| format!("Couldn't read template {}", entry.path().display()))?;
+            overrides.insert(name, &*Box::leak(contents.into_boxed_str()));
+        }
+        Ok(Templates { overrides })
+    }
+
+    /// The template or stylesheet called `name`, overridden or built in
+    pub fn get(&self, name: &str) -> &'static str {
+        self.overrides.get(name).copied().unwrap_or_else(|| {
+            overridable_templates()
+                .into_iter()
+                .find_map(|(known_name, _, template)| (known_name == name).then_some(template))
+                .unwrap_or_else(|| panic!("No template called {name}"))
+        })
+    }
+
+    pub fn is_overridden(&self, name: &str) -> bool {
+        self.overrides.contains_key(name)
+    }
+}
@@ -10,7 +10,7 @@ use regex::Regex;
 use serde_json::Value;
 use std::cell::RefCell;
 use std::fs::{self, File};
-use std::io::{self, BufRead};
+use std::io::{self, BufRead, Read, Seek};
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 use tinytemplate::TinyTemplate;
@@ -20,16 +20,30 @@ use crate::parsers::ParserOutput;
 use crate::parsers::StructuredLogParser;
 use crate::templates::*;
 use crate::types::*;
+pub mod cli;
+pub mod config;
 pub mod parsers;
+pub mod plugins;
+#[cfg(feature = "python")]
+mod python;
+mod search;
+pub mod serve;
+mod summary;
 mod templates;
+#[cfg(feature = "tui")]
+pub mod tui;
 mod types;
 
 pub use types::{
-    ArtifactFlags, CollectiveSchedule, CollectivesParityReport, Diagnostics, DivergenceFlags,
-    DivergenceGroup, ExecOrderSummary, GraphAnalysis, GraphCollectivesParity, GraphRuntime,
-    MultiRankContext, RankMetaData, RuntimeAnalysis, RuntimeRankDetail,
+    ArtifactFlags, ArtifactRecord, CollectiveSchedule, CollectivesParityReport, CompileStatus,
+    DeferredOutput, DeferredOutputs, Diagnostics, DivergenceFlags, DivergenceGroup,
+    ExecOrderSummary, FailureRecord, GraphAnalysis, GraphCollectivesParity, GraphRuntime,
+    MultiRankContext, OutputFile, ParseSummary, RankMetaData, RuntimeAnalysis, RuntimeRankDetail,
+    StackTrieNode,
 };
 
+pub use summary::markdown_summary;
+
 pub use execution_order::{
     analyze_execution_order, parse_graph_execution_order, ExecOrderIndexRow, ExecOrderIssue,
     ExecOrderReport,
@@ -50,6 +64,35 @@ pub struct ParseConfig {
     pub plain_text: bool,
     pub export: bool,
     pub inductor_provenance: bool,
+    // Rendering rules from tlparse.toml
+    pub render_rules: Vec<crate::config::RenderRule>,
+    // Parser names (StructuredLogParser::name) to run exclusively; empty runs all parsers
+    pub only_parsers: Vec<String>,
+    // Parser names to skip; their envelopes fall back to raw payload files
+    pub skip_parsers: Vec<String>,
+    // Store payload dumps once under payloads/<md5> instead of once per compile id
+    pub content_addressed: bool,
+    // Write payloads/ next to the output directory instead of into it, so that the ranks of
+    // --all-ranks-html share one
+    pub shared_payloads: bool,
+    // Templates and stylesheets from --template-dir
+    pub templates: crate::config::Templates,
+}
+
+impl ParseConfig {
+    // Where payload files are written, relative to the output directory
+    pub fn payloads_dir(&self) -> PathBuf {
+        if self.shared_payloads {
+            PathBuf::from("../payloads")
+        } else {
+            PathBuf::from("payloads")
+        }
+    }
+
+    pub fn parser_enabled(&self, name: &str) -> bool {
+        (self.only_parsers.is_empty() || self.only_parsers.iter().any(|p| p == name))
+            && !self.skip_parsers.iter().any(|p| p == name)
+    }
 }
 
 impl Default for ParseConfig {
@@ -63,11 +106,17 @@ impl Default for ParseConfig {
             plain_text: false,
             export: false,
             inductor_provenance: false,
+            render_rules: Vec::default(),
+            only_parsers: Vec::default(),
+            skip_parsers: Vec::default(),
+            content_addressed: false,
+            shared_payloads: false,
+            templates: crate::config::Templates::default(),
         }
     }
 }
 
-fn maybe_remove_convert_frame_suffixes(frames: &mut Vec<FrameSummary>) {
+pub(crate) fn maybe_remove_convert_frame_suffixes(frames: &mut Vec<FrameSummary>) {
     let all_target_frames = [
         [
             ("torch/_dynamo/convert_frame.py", "catch_errors"),
@@ -116,6 +165,7 @@ fn add_unique_suffix(raw_filename: PathBuf, output_count: i32) -> PathBuf {
 }
 
 fn add_file_output(
+    lineno: usize,
     filename: PathBuf,
     content: String,
     output: &mut ParseOutput,
@@ -150,10 +200,34 @@ fn add_file_output(
         number: *output_count,
         suffix: suffix,
         readable_url,
+        group: None,
+        group_start: false,
+        // Only hashed with content_addressed, see parse_log
+        hash: None,
+        lineno: Some(lineno),
     });
     *output_count += 1;
 }
 
+// Cluster outputs that tlparse.toml put in a group after the ungrouped ones, in order of
+// first appearance, and mark where each group starts so the index can label it
+fn group_output_files(files: Vec<OutputFile>) -> Vec<OutputFile> {
+    let (mut grouped, mut ungrouped): (Vec<OutputFile>, Vec<OutputFile>) =
+        files.into_iter().partition(|f| f.group.is_some());
+    let mut groups: FxIndexMap<String, Vec<OutputFile>> = FxIndexMap::default();
+    for file in grouped.drain(..) {
+        groups
+            .entry(file.group.clone().unwrap_or_default())
+            .or_default()
+            .push(file);
+    }
+    for (_, mut files) in groups {
+        files[0].group_start = true;
+        ungrouped.extend(files);
+    }
+    ungrouped
+}
+
 fn is_stack_traces_file(path: &PathBuf) -> bool {
     if let Some(name) = path.file_name().and_then(|s| s.to_str()) {
         name.starts_with("inductor_provenance_tracking_kernel_stack_traces")
@@ -204,6 +278,131 @@ fn add_stack_traces_html(
     html_path_str
 }
 
+// Highlighting runs at well under a MB/s, so huge payloads only get the raw file
+pub(crate) const MAX_HIGHLIGHTED_BYTES: usize = 2 << 20;
+
+// Syntax highlighted, line anchored view of a code output, linked from the index as its readable
+// version.  Unlike the stack traces view it doesn't take up an output number.
+fn add_highlighted_html(
+    file: &mut OutputFile,
+    language: crate::parsers::CodeLanguage,
+    output: &mut ParseOutput,
+    payload_blobs: &mut FxHashSet<PathBuf>,
+    deferred: Option<&mut DeferredOutputs>,
+) -> anyhow::Result<()> {
+    let path = PathBuf::from(&file.url);
+    let html_path = path.with_extension("html");
+    if payload_blobs.contains(&html_path) {
+        // Content addressed payload that was already highlighted
+        file.readable_url = Some(html_path.to_string_lossy().to_string());
+        return Ok(());
+    }
+    let Some((_, content)) = output
+        .iter()
+        .rev()
+        .find(|(p, _)| *p == path)
+        .filter(|(_, content)| content.len() <= MAX_HIGHLIGHTED_BYTES)
+    else {
+        return Ok(());
+    };
+    file.readable_url = Some(html_path.to_string_lossy().to_string());
+    if payload_blobs.contains(&path) {
+        payload_blobs.insert(html_path.clone());
+    }
+    if let Some(deferred) = deferred {
+        deferred.insert(
+            html_path,
+            DeferredOutput::Highlighted {
+                source: path,
+                language,
+            },
+        );
+        return Ok(());
+    }
+    let html = crate::parsers::highlight_anchored_html(content, language)?;
+    output.push((html_path, html));
+    Ok(())
+}
+
+// Payload dumps that provenance tracking pages are built from, by name prefix
+const PROVENANCE_INPUTS: [&str; 7] = [
+    "before_pre_grad_graph",
+    "inductor_pre_grad_graph",
+    "after_post_grad_graph",
+    "inductor_post_grad_graph",
+    "inductor_output_code",
+    "inductor_aot_wrapper_code",
+    "inductor_provenance_tracking_node_mappings",
+];
+
+fn is_provenance_input(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    PROVENANCE_INPUTS
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+// The lines of the envelopes in `spans`, numbered as in the whole log.  Like a full parse, blank
+// lines and lines that aren't UTF-8 are dropped.
+pub(crate) fn read_spans(
+    reader: &mut (impl BufRead + Seek),
+    spans: &[LogSpan],
+) -> io::Result<Vec<(usize, String)>> {
+    let mut lines = Vec::new();
+    for span in spans {
+        reader.seek(io::SeekFrom::Start(span.range.start))?;
+        let mut bytes = Vec::new();
+        reader
+            .by_ref()
+            .take(span.range.end - span.range.start)
+            .read_to_end(&mut bytes)?;
+        for (i, line) in bytes.split(|b| *b == b'\n').enumerate() {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            match std::str::from_utf8(line) {
+                Ok(line) if !line.is_empty() => lines.push((span.lineno + i, line.to_string())),
+                _ => {}
+            }
+        }
+    }
+    Ok(lines)
+}
+
+// What an envelope's payload is, to scope searches by
+fn payload_kind<'p>(
+    e: &Envelope,
+    mut parsers: impl Iterator<Item = &'p dyn StructuredLogParser>,
+) -> String {
+    if let Some(ref artifact) = e.artifact {
+        return artifact.name.clone();
+    }
+    if let Some(ref dump) = e.graph_dump {
+        return dump.name.clone();
+    }
+    parsers
+        .find_map(|parser| match parser.get_metadata(e)? {
+            Metadata::Other(key, _) => Some(key.to_string()),
+            _ => Some(parser.name().to_string()),
+        })
+        .or_else(|| e._other.keys().min().cloned())
+        .unwrap_or_else(|| "payload".to_string())
+}
+
+// Count a parser failure per parser and error kind, and report it
+fn record_parser_error(
+    parser_name: &str,
+    err: &anyhow::Error,
+    multi: &MultiProgress,
+    stats: &mut Stats,
+) {
+    let kind = crate::parsers::ParserError::kind_of(err);
+    multi.suspend(|| eprintln!("Parser {parser_name} failed ({kind}): {err}"));
+    stats.fail_parser += 1;
+    *stats
+        .parser_errors
+        .entry((parser_name.to_string(), kind))
+        .or_default() += 1;
+}
+
 fn run_parser<'t>(
     lineno: usize,
     parser: &Box<dyn StructuredLogParser + 't>,
@@ -224,10 +423,24 @@ fn run_parser<'t>(
                     match parser_result {
                         ParserOutput::File(raw_filename, out) => {
                             let filename = add_unique_suffix(raw_filename, *output_count);
-                            add_file_output(filename, out, output, compile_directory, output_count);
+                            add_file_output(
+                                lineno,
+                                filename,
+                                out,
+                                output,
+                                compile_directory,
+                                output_count,
+                            );
                         }
                         ParserOutput::GlobalFile(filename, out) => {
-                            add_file_output(filename, out, output, compile_directory, output_count);
+                            add_file_output(
+                                lineno,
+                                filename,
+                                out,
+                                output,
+                                compile_directory,
+                                output_count,
+                            );
                         }
                         ParserOutput::PayloadFile(raw_filename) => {
                             let filename = add_unique_suffix(raw_filename, *output_count);
@@ -235,6 +448,7 @@ fn run_parser<'t>(
                                 filename.to_string_lossy().to_string(),
                             );
                             add_file_output(
+                                lineno,
                                 filename,
                                 payload.to_string(),
                                 output,
@@ -250,6 +464,7 @@ fn run_parser<'t>(
                                         filename.to_string_lossy().to_string(),
                                     );
                                     add_file_output(
+                                        lineno,
                                         filename,
                                         formatted_content,
                                         output,
@@ -258,14 +473,11 @@ fn run_parser<'t>(
                                     );
                                 }
                                 Err(err) => {
-                                    multi.suspend(|| {
-                                        eprintln!(
-                                            "Failed to format payload for {}: {}",
-                                            filename.to_string_lossy(),
-                                            err
-                                        )
-                                    });
-                                    stats.fail_parser += 1;
+                                    let err = err.context(format!(
+                                        "Failed to format payload for {}",
+                                        filename.to_string_lossy()
+                                    ));
+                                    record_parser_error(parser.name(), &err, multi, stats);
                                 }
                             }
                         }
@@ -276,22 +488,17 @@ fn run_parser<'t>(
                                 number: *output_count,
                                 suffix: "".to_string(),
                                 readable_url: None,
+                                group: None,
+                                group_start: false,
+                                hash: None,
+                                lineno: Some(lineno),
                             });
                             *output_count += 1;
                         }
                     }
                 }
             }
-            Err(err) => match parser.name() {
-                "dynamo_guards" => {
-                    multi.suspend(|| eprintln!("Failed to parse guards json: {}", err));
-                    stats.fail_dynamo_guards_json += 1;
-                }
-                name => {
-                    multi.suspend(|| eprintln!("Parser {name} failed: {err}"));
-                    stats.fail_parser += 1;
-                }
-            },
+            Err(err) => record_parser_error(parser.name(), &err, multi, stats),
         }
     }
     payload_filename
@@ -317,6 +524,7 @@ fn directory_to_json(
                     "number": file.number,
                     "suffix": file.suffix,
                     "readable_url": file.readable_url,
+                    "hash": file.hash,
                 })
             })
             .collect();
@@ -338,6 +546,7 @@ fn handle_guard(
     multi: &MultiProgress,
     stats: &mut Stats,
     tt: &TinyTemplate,
+    css: &'static str,
     sym_expr_info_index: &RefCell<SymExprInfoIndex>,
     export_failures: &mut Vec<ExportFailure>,
 ) {
@@ -345,6 +554,7 @@ fn handle_guard(
     let parser: Box<dyn StructuredLogParser> =
         Box::new(crate::parsers::PropagateRealTensorsParser {
             tt,
+            css,
             sym_expr_info_index: &sym_expr_info_index_borrowed,
         });
     let _ = run_parser(
@@ -359,10 +569,7 @@ fn handle_guard(
         stats,
     );
 
-    let filename = format!(
-        "symbolic_guard_information_{}.html",
-        (*output_count - 1).to_string()
-    );
+    let filename = format!("symbolic_guard_information_{}.html", *output_count - 1);
     let compile_id_dir: PathBuf = e
         .compile_id
         .as_ref()
@@ -381,14 +588,688 @@ fn handle_guard(
     });
 }
 
+/// Diffs the guard sets of consecutive compiles of each frame, in log order.  Compiles are
+/// grouped by (compiled autograd id, frame id), and each diff is annotated with the recompile
+/// reasons logged for the later compile.  Sets are only compared within one format, preferring
+/// the C++ guard tree when both compiles logged it.
+fn build_guard_diffs(
+    guard_set_index: &GuardSetIndex,
+    recompile_reasons_index: &mut RecompileReasonsIndex,
+) -> Vec<GuardDiff> {
+    let mut last_by_frame: FxHashMap<(Option<u32>, Option<u32>), &Option<CompileId>> =
+        FxHashMap::default();
+    let mut diffs = Vec::new();
+    for (compile_id, guard_sets) in guard_set_index {
+        let Some(cid) = compile_id else {
+            continue;
+        };
+        let frame = (cid.compiled_autograd_id, cid.frame_id);
+        if let Some(prev_compile_id) = last_by_frame.insert(frame, compile_id) {
+            let prev_guard_sets = &guard_set_index[prev_compile_id];
+            let (prev, next) =
+                if !prev_guard_sets.cpp_guards.is_empty() && !guard_sets.cpp_guards.is_empty() {
+                    (&prev_guard_sets.cpp_guards, &guard_sets.cpp_guards)
+                } else if !prev_guard_sets.dynamo_guards.is_empty()
+                    && !guard_sets.dynamo_guards.is_empty()
+                {
+                    (&prev_guard_sets.dynamo_guards, &guard_sets.dynamo_guards)
+                } else {
+                    continue;
+                };
+            let (added, removed, changed, num_unchanged) =
+                crate::parsers::diff_guard_sets(prev, next);
+            diffs.push(GuardDiff {
+                prev_compile_id: prev_compile_id
+                    .as_ref()
+                    .map_or("(unknown)".to_string(), |c| c.to_string()),
+                compile_id: cid.to_string(),
+                added,
+                removed,
+                changed,
+                num_unchanged,
+                recompile_reasons: recompile_reasons_index
+                    .remove(compile_id)
+                    .unwrap_or_default(),
+            });
+        }
+    }
+    diffs
+}
+
+/// Summarizes the cache lookups of the run per cache layer.  Each miss is compared with the
+/// nearest previous lookup of the same layer for the same frame (i.e. code object), to show which
+/// cache key components changed.
+fn build_cache_report(
+    cache_events: &[CacheEvent],
+) -> (
+    Vec<CacheLayerSummary>,
+    Vec<CacheBypassReason>,
+    Vec<CacheMiss>,
+) {
+    // Newer PyTorch logs a *_hash artifact next to the hit/miss artifact for the same lookup
+    let lookups: FxHashSet<(&Option<CompileId>, &str, &Option<String>)> = cache_events
+        .iter()
+        .filter(|e| !e.from_hash_artifact)
+        .map(|e| (&e.compile_id, e.layer.as_str(), &e.key))
+        .collect();
+    let events = cache_events.iter().filter(|e| {
+        !e.from_hash_artifact || !lookups.contains(&(&e.compile_id, e.layer.as_str(), &e.key))
+    });
+
+    let mut layers: FxIndexMap<&str, CacheLayerSummary> = FxIndexMap::default();
+    let mut bypass_reasons: FxIndexMap<(&str, &str), usize> = FxIndexMap::default();
+    let mut misses = Vec::new();
+    // Last lookup with key components, by layer and frame (compiled autograd id, frame id)
+    type Frame = Option<(Option<u32>, Option<u32>)>;
+    let mut last_lookup: FxHashMap<(&str, Frame), &CacheEvent> = FxHashMap::default();
+    for event in events {
+        let frame = event
+            .compile_id
+            .as_ref()
+            .map(|c| (c.compiled_autograd_id, c.frame_id));
+        let summary = layers
+            .entry(&event.layer)
+            .or_insert_with(|| CacheLayerSummary {
+                layer: event.layer.clone(),
+                hits: 0,
+                misses: 0,
+                bypasses: 0,
+                hit_rate: "".to_string(),
+            });
+        match event.state.as_str() {
+            "hit" => summary.hits += 1,
+            "miss" => {
+                summary.misses += 1;
+                let prev = last_lookup.get(&(event.layer.as_str(), frame));
+                misses.push(CacheMiss {
+                    compile_id: event
+                        .compile_id
+                        .as_ref()
+                        .map_or("(unknown)".to_string(), |c| c.to_string()),
+                    layer: event.layer.clone(),
+                    key: event.key.clone().unwrap_or_default(),
+                    prev_compile_id: prev.map(|p| {
+                        p.compile_id
+                            .as_ref()
+                            .map_or("(unknown)".to_string(), |c| c.to_string())
+                    }),
+                    changed: prev.map_or(Vec::new(), |p| {
+                        crate::parsers::diff_cache_components(&p.components, &event.components)
+                    }),
+                });
+            }
+            "bypass" => {
+                summary.bypasses += 1;
+                let reason = event.bypass_reason.as_deref().unwrap_or("(unknown)");
+                *bypass_reasons.entry((&event.layer, reason)).or_default() += 1;
+            }
+            _ => {}
+        }
+        if !event.components.is_empty() {
+            last_lookup.insert((event.layer.as_str(), frame), event);
+        }
+    }
+
+    let layers = layers
+        .into_values()
+        .map(|mut summary| {
+            let total = summary.hits + summary.misses + summary.bypasses;
+            if total > 0 {
+                summary.hit_rate = format!("{:.1}%", 100.0 * summary.hits as f64 / total as f64);
+            }
+            summary
+        })
+        .collect();
+    let bypass_reasons = bypass_reasons
+        .into_iter()
+        .map(|((layer, reason), count)| CacheBypassReason {
+            layer: layer.to_string(),
+            reason: reason.to_string(),
+            count,
+        })
+        .collect();
+    (layers, bypass_reasons, misses)
+}
+
+fn autotune_compile<'a>(
+    autotuning_index: &'a mut AutotuningIndex,
+    compile_id: &Option<CompileId>,
+) -> &'a mut AutotuneCompile {
+    let compile_id = compile_id
+        .as_ref()
+        .map_or("(unknown)".to_string(), |c| c.to_string());
+    autotuning_index
+        .entry(compile_id.clone())
+        .or_insert_with(|| AutotuneCompile {
+            compile_id,
+            ..Default::default()
+        })
+}
+
+// `[["XBLOCK", 1024], ["num_warps", 4]]` as `XBLOCK=1024, num_warps=4`
+fn format_triton_config(config: &[(String, Value)]) -> String {
+    config
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Adds the autotuning time and kernel compile times of a compile's metrics to the autotuning
+/// report.  Kernels already known from its `triton_kernel_info` artifact are left as they are.
+fn record_metrics_autotuning(
+    autotuning_index: &mut AutotuningIndex,
+    compile_id: &Option<CompileId>,
+    m: &CompilationMetricsMetadata,
+) {
+    let kernel_times: Vec<(String, u64)> = m
+        .triton_kernel_compile_times_us
+        .as_deref()
+        .and_then(|times| serde_json::from_str(times).ok())
+        .unwrap_or_default();
+    if m.compile_time_autotune_time_us.is_none() && kernel_times.is_empty() {
+        return;
+    }
+    let compile = autotune_compile(autotuning_index, compile_id);
+    if let Some(us) = m.compile_time_autotune_time_us {
+        compile.autotune_time_s = format!("{:.3}", us as f64 / 1e6);
+    }
+    for (name, us) in kernel_times {
+        if !compile.kernels.iter().any(|k| k.name == name) {
+            compile.kernels.push(AutotunedKernel {
+                name,
+                cache_state: "".to_string(),
+                num_configs: "".to_string(),
+                best_config: "".to_string(),
+                time_ms: format!("{:.1}", us as f64 / 1000.0),
+                time_us: us,
+            });
+        }
+    }
+}
+
+fn autotune_compile_of_event<'a>(
+    autotuning_index: &'a mut AutotuningIndex,
+    compile_id: Option<&str>,
+) -> &'a mut AutotuneCompile {
+    let compile_id = compile_id.map_or("(unknown)".to_string(), |c| format!("[{c}]"));
+    autotuning_index
+        .entry(compile_id.clone())
+        .or_insert_with(|| AutotuneCompile {
+            compile_id,
+            ..Default::default()
+        })
+}
+
+/// The `<op>_template_autotuning` event that `select_algorithm` logs for an op, from its start
+/// event (which carries the input shapes) and end event (which carries the choice stats)
+fn template_autotuning(op: &str, start: &Value, end: &Value) -> TemplateAutotuning {
+    let arg = |name: &str| {
+        end["args"][name]
+            .as_str()
+            .or_else(|| start["args"][name].as_str())
+            .unwrap_or_default()
+    };
+    let stats: AutotuneChoicesStats =
+        serde_json::from_str(arg("autotune_choices_stats")).unwrap_or_default();
+    let ms = |time: Option<f64>| time.map_or("".to_string(), |t| format!("{t:.4}"));
+    let time_us = end["ts"].as_f64().unwrap_or_default() - start["ts"].as_f64().unwrap_or_default();
+    TemplateAutotuning {
+        op: op.to_string(),
+        input_shapes: arg("autotune_shape").to_string(),
+        input_dtypes: arg("autotune_dtypes").to_string(),
+        num_choices: stats.num_choices.map_or("".to_string(), |n| n.to_string()),
+        num_triton_choices: stats
+            .num_triton_choices
+            .map_or("".to_string(), |n| n.to_string()),
+        winner: stats.best_kernel.unwrap_or_default(),
+        winner_desc: stats.best_kernel_desc.unwrap_or_default(),
+        winner_time_ms: ms(stats.best_time),
+        best_triton: stats.best_triton_kernel.unwrap_or_default(),
+        best_triton_desc: stats.best_triton_kernel_desc.unwrap_or_default(),
+        best_triton_time_ms: ms(stats.best_triton_time),
+        time_ms: format!("{:.1}", time_us / 1000.0),
+        time_us,
+    }
+}
+
+/// Attributes `InductorBenchmarker.benchmark_gpu` runs and `<op>_template_autotuning` events to
+/// compile ids.  Events don't always carry a compile id (e.g. when autotuning on the first run
+/// of a graph), so those are attributed to the innermost enclosing event that has one.
+fn record_autotuning_events(autotuning_index: &mut AutotuningIndex, chromium_events: &[Value]) {
+    const BENCHMARK_EVENT: &str = "InductorBenchmarker.benchmark_gpu";
+    const TEMPLATE_AUTOTUNING_SUFFIX: &str = "_template_autotuning";
+    let mut open: Vec<(&str, &str)> = Vec::new();
+    let mut benchmark_start: Option<(f64, Option<&str>)> = None;
+    let mut template_starts: Vec<(&Value, Option<&str>)> = Vec::new();
+    for event in chromium_events {
+        let name = event["name"].as_str().unwrap_or_default();
+        let compile_id = event["args"]["compile_id"]
+            .as_str()
+            .filter(|c| *c != "None");
+        let enclosing_compile_id = compile_id.or_else(|| open.last().map(|(_, c)| *c));
+        match (event["ph"].as_str(), name) {
+            (Some("B"), BENCHMARK_EVENT) => {
+                benchmark_start = event["ts"].as_f64().map(|ts| (ts, enclosing_compile_id));
+                continue;
+            }
+            (Some("E"), BENCHMARK_EVENT) => {
+                let Some((start, compile_id)) = benchmark_start.take() else {
+                    continue;
+                };
+                let compile = autotune_compile_of_event(autotuning_index, compile_id);
+                compile.num_benchmarks += 1;
+                compile.benchmark_time_us += event["ts"].as_f64().unwrap_or(start) - start;
+                compile.benchmark_time_ms = format!("{:.1}", compile.benchmark_time_us / 1000.0);
+                continue;
+            }
+            (Some("B"), _) if name.ends_with(TEMPLATE_AUTOTUNING_SUFFIX) => {
+                template_starts.push((event, enclosing_compile_id));
+            }
+            (Some("E"), _) if name.ends_with(TEMPLATE_AUTOTUNING_SUFFIX) => {
+                if let Some(pos) = template_starts
+                    .iter()
+                    .rposition(|(start, _)| start["name"] == event["name"])
+                {
+                    let (start, compile_id) = template_starts.remove(pos);
+                    let op = name.trim_end_matches(TEMPLATE_AUTOTUNING_SUFFIX);
+                    autotune_compile_of_event(autotuning_index, compile_id)
+                        .ops
+                        .push(template_autotuning(op, start, event));
+                }
+            }
+            _ => {}
+        }
+        match event["ph"].as_str() {
+            Some("B") => {
+                if let Some(compile_id) = compile_id {
+                    open.push((name, compile_id));
+                }
+            }
+            Some("E") => {
+                if let Some(pos) = open.iter().rposition(|(n, _)| *n == name) {
+                    open.remove(pos);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Ranks kernels or ops by their total time, as `(name, (count, time))` pairs
+fn rank_by_time(by_name: FxIndexMap<String, (usize, f64)>) -> Vec<AutotuneTimeSummary> {
+    let total: f64 = by_name.values().map(|(_, t)| t).sum();
+    let mut ranked: Vec<(String, (usize, f64))> = by_name.into_iter().collect();
+    ranked.sort_by(|(_, (_, a)), (_, (_, b))| b.total_cmp(a));
+    ranked
+        .into_iter()
+        .map(|(name, (count, us))| AutotuneTimeSummary {
+            name,
+            count,
+            time_s: format!("{:.3}", us / 1e6),
+            share: if total > 0.0 {
+                format!("{:.1}%", 100.0 * us / total)
+            } else {
+                "".to_string()
+            },
+        })
+        .collect()
+}
+
+/// Renders the autotuning report.  Ops are ranked by their autotune time and kernels by their
+/// compile time, in each compile and across the run.
+fn autotuning_context(autotuning_index: AutotuningIndex, css: &'static str) -> AutotuningContext {
+    let mut compiles: Vec<AutotuneCompile> = autotuning_index.into_values().collect();
+    let mut by_kernel: FxIndexMap<String, (usize, f64)> = FxIndexMap::default();
+    let mut by_op: FxIndexMap<String, (usize, f64)> = FxIndexMap::default();
+    let (mut num_benchmarks, mut benchmark_time_us, mut autotune_time_s) = (0, 0.0, 0.0);
+    for compile in compiles.iter_mut() {
+        compile
+            .kernels
+            .sort_by(|a, b| b.time_us.cmp(&a.time_us).then_with(|| a.name.cmp(&b.name)));
+        for kernel in &compile.kernels {
+            let entry = by_kernel.entry(kernel.name.clone()).or_default();
+            entry.0 += 1;
+            entry.1 += kernel.time_us as f64;
+        }
+        compile.ops.sort_by(|a, b| b.time_us.total_cmp(&a.time_us));
+        for op in &compile.ops {
+            let entry = by_op.entry(op.op.clone()).or_default();
+            entry.0 += 1;
+            entry.1 += op.time_us;
+        }
+        num_benchmarks += compile.num_benchmarks;
+        benchmark_time_us += compile.benchmark_time_us;
+        autotune_time_s += compile.autotune_time_s.parse::<f64>().unwrap_or(0.0);
+    }
+    let kernel_time_us: f64 = by_kernel.values().map(|(_, us)| us).sum();
+    let op_time_us: f64 = by_op.values().map(|(_, us)| us).sum();
+    AutotuningContext {
+        css,
+        kernel_time_s: format!("{:.3}", kernel_time_us / 1e6),
+        num_benchmarks,
+        benchmark_time_s: format!("{:.3}", benchmark_time_us / 1e6),
+        autotune_time_s: if autotune_time_s > 0.0 {
+            format!("{:.3}", autotune_time_s)
+        } else {
+            "".to_string()
+        },
+        op_time_s: format!("{:.3}", op_time_us / 1e6),
+        ops: rank_by_time(by_op),
+        kernels: rank_by_time(by_kernel),
+        compiles,
+        qps: TEMPLATE_QUERY_PARAM_SCRIPT,
+    }
+}
+
+fn record_feature_usage(
+    feature_usage_index: &mut FxIndexMap<String, FeatureUsageRow>,
+    compile_id: &Option<CompileId>,
+    feature: &str,
+    value: bool,
+) {
+    let compile_id = compile_id
+        .as_ref()
+        .map_or("(unknown)".to_string(), |c| c.to_string());
+    let row = feature_usage_index
+        .entry(feature.to_string())
+        .or_insert_with(|| FeatureUsageRow {
+            feature: feature.to_string(),
+            enabled_in: Vec::new(),
+            disabled_in: Vec::new(),
+        });
+    let compile_ids = if value {
+        &mut row.enabled_in
+    } else {
+        &mut row.disabled_in
+    };
+    if !compile_ids.contains(&compile_id) {
+        compile_ids.push(compile_id);
+    }
+}
+
+/// Records the symbolic shape events of an envelope into the run-wide dynamic shapes index.
+/// Symbols are listed in creation order, per compile id; any later event of the same compile
+/// whose expression mentions one is listed as a use of it.
+fn record_dynamic_shapes_event(index: &mut DynamicShapesIndex, e: &Envelope) {
+    static IDENT_RE: once_cell::sync::Lazy<Regex> =
+        once_cell::sync::Lazy::new(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").unwrap());
+    let compile_id = e
+        .compile_id
+        .as_ref()
+        .map_or("(unknown)".to_string(), |c| c.to_string());
+    let user_stack_html = |stack: &Option<StackSummary>| {
+        stack.as_ref().map_or("".to_string(), |s| {
+            crate::parsers::format_stack(s, "User Stack", false)
+        })
+    };
+    let new_symbol = |symbol: &str, unbacked: bool| DynamicSymbolContext {
+        compile_id: compile_id.clone(),
+        symbol: symbol.to_string(),
+        unbacked,
+        sources: Vec::new(),
+        value: "".to_string(),
+        value_range: "".to_string(),
+        user_stack_html: "".to_string(),
+        uses: Vec::new(),
+    };
+
+    // (kind, expression) of events that can mention symbols created earlier
+    let mut mentioned: Vec<(&'static str, &str)> = Vec::new();
+    if let Some(ref m) = e.create_symbol {
+        if let Some(ref symbol) = m.symbol {
+            let key = (compile_id.clone(), symbol.clone());
+            match index.symbols.get_mut(&key) {
+                // Created again for another source, e.g. with duck sizing
+                Some(entry) => {
+                    if let Some(ref source) = m.source {
+                        if !entry.sources.contains(source) {
+                            entry.sources.push(source.clone());
+                        }
+                        entry.uses.push(DynamicSymbolUseContext {
+                            kind: "create_symbol",
+                            expr: format!("{source} = {}", m.val.clone().unwrap_or_default()),
+                        });
+                    }
+                }
+                None => {
+                    let mut entry = new_symbol(symbol, false);
+                    entry.sources.extend(m.source.clone());
+                    entry.value = m.val.clone().unwrap_or_default();
+                    entry.value_range = m.vr.clone().unwrap_or_default();
+                    entry.user_stack_html = user_stack_html(&m.user_stack);
+                    index.symbols.insert(key, entry);
+                }
+            }
+        }
+    }
+    if let Some(ref m) = e.create_unbacked_symbol {
+        if let Some(ref symbol) = m.symbol {
+            let mut entry = new_symbol(symbol, true);
+            entry.value_range = m.vr.clone().unwrap_or_default();
+            entry.user_stack_html = user_stack_html(&m.user_stack);
+            index
+                .symbols
+                .entry((compile_id.clone(), symbol.clone()))
+                .or_insert(entry);
+        }
+    }
+    if let Some(ref m) = e.expression_created {
+        mentioned.extend(m.result.iter().map(|r| ("expression", r.as_str())));
+    }
+    if let Some(ref m) = e.symbolic_shape_specialization {
+        let symbol = m.symbol.clone().unwrap_or_default();
+        let sources = m.sources.clone().unwrap_or_default();
+        if !symbol.is_empty() {
+            let entry = index
+                .symbols
+                .entry((compile_id.clone(), symbol.clone()))
+                .or_insert_with(|| new_symbol(&symbol, false));
+            for source in &sources {
+                if !entry.sources.contains(source) {
+                    entry.sources.push(source.clone());
+                }
+            }
+        }
+        index.specializations.push(DynamicSpecializationContext {
+            compile_id: compile_id.clone(),
+            symbol,
+            sources,
+            value: m.value.clone().unwrap_or_default(),
+            reason: m.reason.clone().unwrap_or_default(),
+            user_stack_html: user_stack_html(&m.user_stack),
+        });
+        mentioned.extend(m.reason.iter().map(|r| ("specialization", r.as_str())));
+    }
+    if let Some(ref m) = e.guard_added_fast {
+        let expr = m.expr.clone().unwrap_or_default();
+        index.guards.push(DynamicGuardContext {
+            compile_id: compile_id.clone(),
+            expr,
+            user_stack_html: user_stack_html(&m.user_stack),
+        });
+        mentioned.extend(m.expr.iter().map(|s| ("guard", s.as_str())));
+    }
+
+    for (kind, expr) in mentioned {
+        let mut seen: Vec<&str> = Vec::new();
+        for ident in IDENT_RE.find_iter(expr) {
+            if seen.contains(&ident.as_str()) {
+                continue;
+            }
+            seen.push(ident.as_str());
+            let key = (compile_id.clone(), ident.as_str().to_string());
+            if let Some(entry) = index.symbols.get_mut(&key) {
+                entry.uses.push(DynamicSymbolUseContext {
+                    kind,
+                    expr: expr.to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// Run-wide reports parse_path renders after the last line, with the envelope keys they read.
+/// `tlparse parsers` lists them, and --only-parsers / --skip-parsers turn them off like parsers.
+const RUN_WIDE_REPORTS: &[(&str, &[&str])] = &[
+    (
+        "guard_diff",
+        &["dynamo_guards", "dynamo_cpp_guards_str", "artifact"],
+    ),
+    ("kernels", &["inductor_output_code", "artifact"]),
+    ("feature_usage", &["compilation_metrics"]),
+    (
+        "autotuning",
+        &[
+            "compilation_metrics",
+            "compilation_metrics_runtime",
+            "artifact",
+            "chromium_event",
+        ],
+    ),
+    ("cache_report", &["artifact"]),
+    (
+        "dynamic_shapes",
+        &[
+            "create_symbol",
+            "create_unbacked_symbol",
+            "expression_created",
+            "symbolic_shape_specialization",
+            "guard_added_fast",
+        ],
+    ),
+];
+
+/// All parsers parse_path runs with this config (ignoring --only-parsers / --skip-parsers),
+/// as (name, envelope keys consumed).
+pub fn registered_parsers(config: &ParseConfig) -> Vec<(&'static str, Vec<String>)> {
+    let tt = TinyTemplate::new();
+    let parsers = default_parsers(&tt, config);
+    let mut result: Vec<(&'static str, Vec<String>)> = parsers
+        .iter()
+        .chain(config.custom_parsers.iter())
+        .map(|parser| (parser.name(), parser.envelope_keys()))
+        .collect();
+    result.extend(run_wide_parsers(config));
+    if !config.export {
+        result.extend(
+            RUN_WIDE_REPORTS
+                .iter()
+                .map(|(name, keys)| (*name, keys.iter().map(|k| k.to_string()).collect())),
+        );
+    }
+    result
+}
+
+/// The parsers parse_path builds per envelope because they need run-wide indexes, as (name,
+/// envelope keys consumed)
+fn run_wide_parsers(config: &ParseConfig) -> Vec<(&'static str, Vec<String>)> {
+    let tt = TinyTemplate::new();
+    if !config.export {
+        // These need run-wide indexes, so parse_path runs them outside of default_parsers
+        let tensor_description_index = RefCell::new(TensorDescriptionIndex::default());
+        let stack_index = RefCell::new(StackIndex::default());
+        let symbolic_shape_specialization_index =
+            RefCell::new(SymbolicShapeSpecializationIndex::default());
+        let guard_added_fast_index = RefCell::new(GuardAddedFastIndex::default());
+        let sym_expr_info_index = SymExprInfoIndex::default();
+        let output_files = Vec::new();
+        let compile_id_dir = PathBuf::new();
+        let metrics_pages = RefCell::new(CompilationMetricsPageIndex::default());
+        // The parser that renders an envelope's own page comes first
+        let run_wide: Vec<Box<dyn StructuredLogParser>> = vec![
+            Box::new(crate::parsers::CompilationMetricsParser {
+                tt: &tt,
+                css: config.templates.get("main.css"),
+                stack_index: &stack_index,
+                symbolic_shape_specialization_index: &symbolic_shape_specialization_index,
+                guard_added_fast_index: &guard_added_fast_index,
+                output_files: &output_files,
+                compile_id_dir: &compile_id_dir,
+                tensor_inputs_url: None,
+                metrics_pages: &metrics_pages,
+            }),
+            Box::new(crate::parsers::TensorInputsParser {
+                tt: &tt,
+                css: config.templates.get("tensor_inputs.css"),
+                tensor_description_index: &tensor_description_index,
+            }),
+            Box::new(crate::parsers::PropagateRealTensorsParser {
+                tt: &tt,
+                css: config.templates.get("main.css"),
+                sym_expr_info_index: &sym_expr_info_index,
+            }),
+        ];
+        run_wide
+            .iter()
+            .map(|parser| (parser.name(), parser.envelope_keys()))
+            .collect()
+    } else {
+        // Export reports these as failures instead, but they can still be skipped
+        vec![(
+            "guard_added",
+            vec![
+                "guard_added".to_string(),
+                "propagate_real_tensors_provenance".to_string(),
+            ],
+        )]
+    }
+}
+
 pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseOutput> {
+    parse_log(path, config, None, None).map(|(output, _)| output)
+}
+
+/// Like `parse_path`, also returning the records the report was rendered from, with the content
+/// of each artifact.
+pub fn parse_path_summary(
+    path: &PathBuf,
+    config: &ParseConfig,
+) -> anyhow::Result<(ParseOutput, ParseSummary)> {
+    let (output, mut summary) = parse_log(path, config, None, None)?;
+    let contents: FxHashMap<&Path, &String> =
+        output.iter().map(|(p, c)| (p.as_path(), c)).collect();
+    for artifact in &mut summary.artifacts {
+        artifact.content = contents
+            .get(Path::new(&artifact.url))
+            .map(|c| c.to_string());
+    }
+    Ok((output, summary))
+}
+
+/// Like `parse_path`, but leaves the payload dumps, their highlighted views and raw.log out of
+/// the output, listing what each is produced from instead.  This is most of the rendering work
+/// and memory of a big log, and `tlparse serve` only produces them when they're requested.
+pub fn parse_path_deferred(
+    path: &PathBuf,
+    config: &ParseConfig,
+) -> anyhow::Result<(ParseOutput, DeferredOutputs)> {
+    let mut deferred = DeferredOutputs::default();
+    let (output, _) = parse_log(path, config, Some(&mut deferred), None)?;
+    Ok((output, deferred))
+}
+
+// Parses the log at `path`, or only the envelopes of it in `spans`, into its output and the
+// records it was rendered from
+fn parse_log(
+    path: &PathBuf,
+    config: &ParseConfig,
+    mut deferred: Option<&mut DeferredOutputs>,
+    spans: Option<&[LogSpan]>,
+) -> anyhow::Result<(ParseOutput, ParseSummary)> {
     let strict = config.strict;
     if !path.is_file() {
         bail!("{} is not a file", path.display())
     }
     let file = File::open(path)?;
     let metadata = file.metadata()?;
-    let file_size = metadata.len();
+    let file_size = match spans {
+        Some(spans) => spans
+            .iter()
+            .map(|span| span.range.end - span.range.start)
+            .sum(),
+        None => metadata.len(),
+    };
 
     // TODO: abstract out this spinner to not be part of the library
     // Instead, add a callback trait for CLIs to implement
@@ -399,7 +1280,7 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
         .progress_chars("#>-"));
     let spinner = multi.add(ProgressBar::new_spinner());
 
-    let reader = io::BufReader::new(file);
+    let mut reader = io::BufReader::new(file);
 
     let re_glog = Regex::new(concat!(
         r"(?<level>[VIWEC])(?<month>\d{2})(?<day>\d{2}) ",
@@ -456,14 +1337,33 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
     // For files, link and rendered name are the same
     // For links, you can specify a custom name for the link
     let mut directory: FxIndexMap<Option<CompileId>, Vec<OutputFile>> = FxIndexMap::default();
+    let mut summary = ParseSummary::default();
 
     let mut metrics_index: CompilationMetricsIndex = FxIndexMap::default();
+    // The same metrics by the exact compile id they were logged with, attempt included
+    let mut attempt_metrics_index: CompilationMetricsIndex = FxIndexMap::default();
     let stack_index: RefCell<StackIndex> = RefCell::new(FxHashMap::default());
 
     let symbolic_shape_specialization_index: RefCell<SymbolicShapeSpecializationIndex> =
         RefCell::new(FxHashMap::default());
     let guard_added_fast_index: RefCell<GuardAddedFastIndex> = RefCell::new(FxHashMap::default());
     let sym_expr_info_index: RefCell<SymExprInfoIndex> = RefCell::new(FxHashMap::default());
+    let tensor_description_index: RefCell<TensorDescriptionIndex> =
+        RefCell::new(FxHashMap::default());
+    let mut guard_set_index: GuardSetIndex = FxIndexMap::default();
+    let mut recompile_reasons_index: RecompileReasonsIndex = FxHashMap::default();
+    let mut dynamic_shapes_index = DynamicShapesIndex::default();
+    let mut cache_events: Vec<CacheEvent> = Vec::new();
+    let cache_artifact_re = Regex::new(r"^(\w+_cache)_(hit|miss|bypass|hash)$")?;
+    let mut kernel_inventory: KernelInventory = FxIndexMap::default();
+    let mut num_kernel_definitions = 0;
+    let mut fallback_compiles: Vec<FallbackCompile> = Vec::new();
+    let mut triton_kernel_info_index: FxHashMap<(String, String), String> = FxHashMap::default();
+    let mut autotuning_index: AutotuningIndex = FxIndexMap::default();
+    let mut metrics_page_index: FxHashMap<Option<CompileId>, PathBuf> = FxHashMap::default();
+    let metrics_pages: RefCell<CompilationMetricsPageIndex> = RefCell::new(FxHashMap::default());
+    let mut runtime_metrics_index: RuntimeMetricsIndex = FxHashMap::default();
+    let mut feature_usage_index: FxIndexMap<String, FeatureUsageRow> = FxIndexMap::default();
 
     // Store results in an output ParseOutput
     let mut output: ParseOutput = Vec::new();
@@ -474,55 +1374,89 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
     let mut tt: TinyTemplate = TinyTemplate::new();
     tt.add_formatter("format_unescaped", tinytemplate::format_unescaped);
     if config.export {
-        tt.add_template("index.html", TEMPLATE_EXPORT_INDEX)?;
-        tt.add_template(
-            "symbolic_guard_information.html",
-            TEMPLATE_SYMBOLIC_GUARD_INFO,
-        )?;
+        tt.add_template("index.html", config.templates.get("export_index.html"))?;
     } else {
-        tt.add_template("index.html", TEMPLATE_INDEX)?;
-        tt.add_template("failures_and_restarts.html", TEMPLATE_FAILURES_AND_RESTARTS)?;
-        tt.add_template("dynamo_guards.html", TEMPLATE_DYNAMO_GUARDS)?;
-        tt.add_template("compilation_metrics.html", TEMPLATE_COMPILATION_METRICS)?;
-        tt.add_template(
+        for name in [
+            "index.html",
+            "failures_and_restarts.html",
+            "dynamo_guards.html",
+            "guard_diff.html",
+            "dynamic_shapes.html",
+            "cache_report.html",
+            "kernels.html",
+            "autotuning.html",
+            "runtime_metrics_table.html",
+            "compilation_metrics_runtime.html",
+            "feature_usage.html",
+            "compilation_metrics.html",
+            "tensor_inputs.html",
+            "json_table.html",
+            "markdown.html",
             "bwd_compilation_metrics.html",
-            TEMPLATE_BWD_COMPILATION_METRICS,
-        )?;
-        tt.add_template(
             "aot_autograd_backward_compilation_metrics.html",
-            TEMPLATE_AOT_AUTOGRAD_BACKWARD_COMPILATION_METRICS,
-        )?;
+        ] {
+            tt.add_template(name, config.templates.get(name))?;
+        }
+    }
+    for name in [
+        "symbolic_guard_information.html",
+        "provenance_tracking.html",
+    ] {
+        tt.add_template(name, config.templates.get(name))?;
     }
-    tt.add_template("provenance_tracking.html", TEMPLATE_PROVENANCE_TRACKING)?;
 
-    let mut unknown_fields: FxHashSet<String> = FxHashSet::default();
+    // Unknown envelope keys rendered by the fallback renderer, with occurrence counts
+    let mut unknown_fields: FxIndexMap<String, u64> = FxIndexMap::default();
 
     let mut output_count = 0;
+    // Files stored so far under payloads/, and (for content_addressed) the per compile id files
+    // they stand in for
+    let mut payload_blobs: FxHashSet<PathBuf> = FxHashSet::default();
+    let mut payload_aliases: Vec<(PathBuf, PathBuf)> = Vec::new();
+    // Deferred payload dumps that provenance tracking reads
+    let mut provenance_inputs: ParseOutput = Vec::new();
 
     let mut breaks = RestartsAndFailuresContext {
-        css: TEMPLATE_FAILURES_CSS,
+        css: config.templates.get("failures.css"),
         failures: Vec::new(),
         qps: TEMPLATE_QUERY_PARAM_SCRIPT,
     };
 
     let mut export_failures: Vec<ExportFailure> = Vec::new();
+    let mut search_index = crate::search::SearchIndex::default();
 
     // NB: Sometimes, the log output we get from Logarithm stutters with a blank line.
     // Filter them out, they're never valid (a blank line in payload will still be \t)
-    let mut iter = reader
-        .lines()
-        .enumerate()
-        .filter_map(|(i, l)| match l {
+    let lines: Box<dyn Iterator<Item = (usize, String)>> = match spans {
+        Some(spans) => Box::new(read_spans(&mut reader, spans)?.into_iter()),
+        None => Box::new(reader.lines().enumerate().filter_map(|(i, l)| match l {
             // 1-indexed line numbers please
             Ok(l) if !l.is_empty() => Some((i + 1, l)),
             _ => None,
-        })
-        .peekable();
+        })),
+    };
+    let mut iter = lines.peekable();
 
     let default_parsers = default_parsers(&tt, config);
-    let mut all_parsers: Vec<&Box<dyn StructuredLogParser>> = default_parsers.iter().collect();
+    let (all_parsers, skipped_parsers): (Vec<&Box<dyn StructuredLogParser>>, Vec<_>) =
+        default_parsers
+            .iter()
+            .chain(config.custom_parsers.iter())
+            .partition(|parser| config.parser_enabled(parser.name()));
+    let run_wide_parsers = run_wide_parsers(config);
+    let any_run_wide_parser_skipped = run_wide_parsers
+        .iter()
+        .any(|(name, _)| !config.parser_enabled(name));
     let mut chromium_events: Vec<serde_json::Value> = Vec::new();
-    all_parsers.extend(config.custom_parsers.iter());
+    let known_parsers: Vec<&'static str> = registered_parsers(config)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    for name in config.only_parsers.iter().chain(config.skip_parsers.iter()) {
+        if !known_parsers.contains(&name.as_str()) {
+            eprintln!("Unknown parser {name}, see `tlparse parsers` for the list of parsers");
+        }
+    }
 
     while let Some((lineno, line)) = iter.next() {
         bytes_read += line.len() as u64;
@@ -666,15 +1600,6 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
             }
         };
 
-        stats.unknown += e._other.len() as u64;
-
-        for k in e._other.keys() {
-            unknown_fields.insert(k.clone());
-            if config.verbose {
-                multi.suspend(|| eprintln!("Unknown field {}", k))
-            }
-        }
-
         if let Some((s, i)) = e.str {
             let mut intern_table = INTERN_TABLE.lock().unwrap();
             intern_table.insert(i, s);
@@ -740,8 +1665,9 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
         }
 
         // TODO: output should be able to generate this without explicitly creating
-        let compile_directory = directory.entry(compile_id_entry).or_default();
+        let compile_directory = directory.entry(compile_id_entry.clone()).or_default();
 
+        let first_output = compile_directory.len();
         let mut parser_payload_filename = ParserResult::NoPayload;
         for parser in &all_parsers {
             let result = run_parser(
@@ -761,53 +1687,335 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
             }
         }
 
-        if let Some(ref m) = e.compilation_metrics {
-            let copied_directory = compile_directory.clone();
-            let compile_id_dir: PathBuf = e
-                .compile_id
-                .as_ref()
-                .map_or(format!("unknown_{lineno}"), |cid| cid.as_directory_name())
-                .into();
-            let parser: Box<dyn StructuredLogParser> =
-                Box::new(crate::parsers::CompilationMetricsParser {
-                    tt: &tt,
-                    stack_index: &stack_index,
-                    symbolic_shape_specialization_index: &symbolic_shape_specialization_index,
-                    guard_added_fast_index: &guard_added_fast_index,
-                    output_files: &copied_directory,
-                    compile_id_dir: &compile_id_dir,
-                });
-            let result = run_parser(
+        // Envelopes whose parser was skipped still get their raw payload written out, or
+        // their metadata if they have no payload; unknown keys are covered by the fallback
+        // renderer below
+        let skipped = skipped_parsers
+            .iter()
+            .find_map(|parser| match parser.get_metadata(&e) {
+                Some(Metadata::Other(..)) | None => None,
+                Some(Metadata::Artifact(a)) => Some((a.name.clone(), parser.envelope_keys())),
+                Some(_) => Some((parser.name().to_string(), parser.envelope_keys())),
+            })
+            .or_else(|| {
+                // The run-wide parsers aren't in skipped_parsers, so go by the keys they read.
+                // An envelope belongs to the first one that reads it.
+                if !any_run_wide_parser_skipped {
+                    return None;
+                }
+                let envelope: serde_json::Value =
+                    serde_json::from_str(original_json_envelope).ok()?;
+                run_wide_parsers
+                    .iter()
+                    .find(|(_, keys)| keys.iter().any(|k| envelope.get(k).is_some()))
+                    .filter(|(name, _)| !config.parser_enabled(name))
+                    .map(|(name, keys)| (name.to_string(), keys.clone()))
+            });
+        if let Some((name, keys)) = skipped {
+            let (filename, content) = if !payload.is_empty() {
+                (format!("{name}.txt"), payload.clone())
+            } else {
+                let envelope: serde_json::Value =
+                    serde_json::from_str(original_json_envelope).unwrap_or_default();
+                let metadata = keys
+                    .iter()
+                    .find_map(|key| envelope.get(key))
+                    .cloned()
+                    .unwrap_or_default();
+                (
+                    format!("{name}.json"),
+                    serde_json::to_string_pretty(&metadata)?,
+                )
+            };
+            let filename = crate::parsers::build_file_path(&filename, lineno, &e.compile_id);
+            let filename = add_unique_suffix(filename, output_count);
+            parser_payload_filename =
+                ParserResult::PayloadFilename(filename.to_string_lossy().to_string());
+            add_file_output(
                 lineno,
-                &parser,
-                &e,
-                &payload,
-                &mut output_count,
+                filename,
+                content,
                 &mut output,
                 compile_directory,
-                &multi,
-                &mut stats,
+                &mut output_count,
             );
-            // Take the last PayloadFilename entry as per the requirement
-            if matches!(result, ParserResult::PayloadFilename(_)) {
-                parser_payload_filename = result;
+        }
+
+        // With content_addressed, the payload dump is stored once as payloads/<md5>.<ext>, and
+        // the compile directory entry and raw.jsonl reference the shared copy
+        if config.content_addressed {
+            if let ParserResult::PayloadFilename(ref filename) = parser_payload_filename {
+                if let Some(file) = compile_directory[first_output..]
+                    .iter_mut()
+                    .find(|f| f.url == *filename)
+                {
+                    let path = PathBuf::from(filename);
+                    if let Some(i) = output.iter().rposition(|(p, _)| *p == path) {
+                        let hash = format!("{:x}", Md5::digest(output[i].1.as_bytes()));
+                        let mut blob = config.payloads_dir().join(&hash);
+                        if let Some(extension) = path.extension() {
+                            blob.set_extension(extension);
+                        }
+                        file.hash = Some(hash);
+                        if payload_blobs.insert(blob.clone()) {
+                            output[i].0 = blob.clone();
+                        } else {
+                            output.remove(i);
+                        }
+                        file.url = blob.to_string_lossy().to_string();
+                        parser_payload_filename = ParserResult::PayloadFilename(file.url.clone());
+                        payload_aliases.push((path, blob));
+                    }
+                }
             }
+        }
 
-            // compilation metrics is always the last output, since it just ran
-            let metrics_filename = format!(
-                "compilation_metrics_{}.html",
-                (output_count - 1).to_string(),
-            );
-            let id = e.compile_id.clone().map_or("(unknown) ".to_string(), |c| {
-                format!(
-                    "<a href='{}/{}'>{cid}</a> ",
-                    compile_id_dir.display(),
-                    metrics_filename,
-                    cid = c,
-                )
-            });
+        // Plain text code outputs get a highlighted view; --plain-text is for diffing, so it
+        // skips them
+        if !config.plain_text {
+            if let Some(language) = crate::parsers::CodeLanguage::of_envelope(&e) {
+                for file in compile_directory[first_output..]
+                    .iter_mut()
+                    .filter(|f| f.url.ends_with(".txt") && f.readable_url.is_none())
+                {
+                    if let Err(err) = add_highlighted_html(
+                        file,
+                        language,
+                        &mut output,
+                        &mut payload_blobs,
+                        deferred.as_deref_mut(),
+                    ) {
+                        record_parser_error("highlighter", &err, &multi, &mut stats);
+                    }
+                }
+            }
+        }
+
+        // Deferred payload dumps are read back from the log when needed; provenance tracking
+        // reads the ones it uses at the end, so those are kept aside until then
+        if let Some(ref mut deferred) = deferred {
+            if let ParserResult::PayloadFilename(ref filename) = parser_payload_filename {
+                let path = PathBuf::from(filename);
+                if let Some(i) = output
+                    .iter()
+                    .rposition(|(p, content)| *p == path && *content == payload)
+                {
+                    let (path, content) = output.remove(i);
+                    // Content addressed payloads are matched by the name they stand in for
+                    let name = payload_aliases
+                        .last()
+                        .filter(|(_, blob)| *blob == path)
+                        .map_or(&path, |(alias, _)| alias);
+                    if config.inductor_provenance && is_provenance_input(name) {
+                        provenance_inputs.push((path.clone(), content));
+                    }
+                    deferred.insert(path, DeferredOutput::Payload { lineno });
+                }
+            }
+        }
+
+        // Keys claimed by a custom parser via Metadata::Other aren't unknown
+        let claimed_keys: FxHashSet<&str> = all_parsers
+            .iter()
+            .filter_map(|parser| match parser.get_metadata(&e) {
+                Some(Metadata::Other(key, _)) => Some(key),
+                _ => None,
+            })
+            .collect();
+        let mut unknown_keys: Vec<&String> = e
+            ._other
+            .keys()
+            .filter(|k| !claimed_keys.contains(k.as_str()))
+            .collect();
+        unknown_keys.sort();
+        stats.unknown += unknown_keys.len() as u64;
+        for key in unknown_keys {
+            *unknown_fields.entry(key.clone()).or_default() += 1;
+            if config.verbose {
+                multi.suspend(|| eprintln!("Unknown field {}", key))
+            }
+            match crate::parsers::render_unknown_envelope(
+                key,
+                &e._other[key],
+                lineno,
+                &e.compile_id,
+                &payload,
+            ) {
+                Ok(results) => {
+                    for result in results {
+                        if let ParserOutput::File(raw_filename, out) = result {
+                            let filename = add_unique_suffix(raw_filename, output_count);
+                            add_file_output(
+                                lineno,
+                                filename,
+                                out,
+                                &mut output,
+                                compile_directory,
+                                &mut output_count,
+                            );
+                        }
+                    }
+                }
+                Err(err) => record_parser_error("unknown_envelope", &err, &multi, &mut stats),
+            }
+        }
+
+        if let Some((rule, _)) = crate::config::find_rule(&config.render_rules, &e) {
+            if rule.hidden {
+                compile_directory.truncate(first_output);
+            }
+            for output_file in &mut compile_directory[first_output..] {
+                if let Some(ref display_name) = rule.display_name {
+                    output_file.name = display_name.clone();
+                }
+                output_file.group = rule.group.clone();
+            }
+        }
+
+        // Index the payload for the search box, linking to its line anchored view if it has one
+        if !config.export && !payload.is_empty() && e.chromium_event.is_none() {
+            let outputs = &compile_directory[first_output..];
+            let payload_file = match parser_payload_filename {
+                ParserResult::PayloadFilename(ref filename) => {
+                    outputs.iter().find(|f| f.url == *filename)
+                }
+                ParserResult::NoPayload => None,
+            };
+            let target = match payload_file {
+                Some(OutputFile {
+                    url,
+                    readable_url: Some(readable_url),
+                    ..
+                }) if url.ends_with(".txt") && readable_url.ends_with(".html") => {
+                    Some((readable_url, true))
+                }
+                Some(file) => Some((&file.url, false)),
+                None => outputs.first().map(|file| (&file.url, false)),
+            };
+            if let Some((url, anchored)) = target {
+                search_index.add(
+                    compile_id_entry
+                        .as_ref()
+                        .map_or("(unknown)".to_string(), |id| id.to_string()),
+                    payload_kind(&e, all_parsers.iter().map(|parser| parser.as_ref())),
+                    url.clone(),
+                    anchored,
+                    &payload,
+                );
+            }
+        }
+
+        if let Some(ref m) = e.compilation_metrics {
+            add_compilation_metrics(&mut summary, original_json_envelope)?;
+            if !config.export {
+                record_metrics_autotuning(&mut autotuning_index, &e.compile_id, m);
+            }
+            // Listed like every other page, and linked from the compilation metrics page
+            let mut tensor_inputs_url = None;
+            if config.parser_enabled("tensor_inputs") {
+                let parser: Box<dyn StructuredLogParser> =
+                    Box::new(crate::parsers::TensorInputsParser {
+                        tt: &tt,
+                        css: config.templates.get("tensor_inputs.css"),
+                        tensor_description_index: &tensor_description_index,
+                    });
+                let first_inputs_output = compile_directory.len();
+                run_parser(
+                    lineno,
+                    &parser,
+                    &e,
+                    &payload,
+                    &mut output_count,
+                    &mut output,
+                    compile_directory,
+                    &multi,
+                    &mut stats,
+                );
+                tensor_inputs_url = compile_directory[first_inputs_output..]
+                    .last()
+                    .and_then(|f| Path::new(&f.url).file_name())
+                    .map(|f| f.to_string_lossy().to_string());
+            }
+
+            let compile_id_dir: PathBuf = e
+                .compile_id
+                .as_ref()
+                .map_or(format!("unknown_{lineno}"), |cid| cid.as_directory_name())
+                .into();
+            let metrics_filename = if config.parser_enabled("compilation_metrics") {
+                let copied_directory = compile_directory.clone();
+                let parser: Box<dyn StructuredLogParser> =
+                    Box::new(crate::parsers::CompilationMetricsParser {
+                        tt: &tt,
+                        css: config.templates.get("main.css"),
+                        stack_index: &stack_index,
+                        symbolic_shape_specialization_index: &symbolic_shape_specialization_index,
+                        guard_added_fast_index: &guard_added_fast_index,
+                        output_files: &copied_directory,
+                        compile_id_dir: &compile_id_dir,
+                        tensor_inputs_url,
+                        metrics_pages: &metrics_pages,
+                    });
+                let result = run_parser(
+                    lineno,
+                    &parser,
+                    &e,
+                    &payload,
+                    &mut output_count,
+                    &mut output,
+                    compile_directory,
+                    &multi,
+                    &mut stats,
+                );
+                // Take the last PayloadFilename entry as per the requirement
+                if matches!(result, ParserResult::PayloadFilename(_)) {
+                    parser_payload_filename = result;
+                }
+
+                // compilation metrics is always the last output, since it just ran
+                Some(format!("compilation_metrics_{}.html", output_count - 1))
+            } else {
+                None
+            };
+            let id =
+                e.compile_id
+                    .clone()
+                    .map_or("(unknown) ".to_string(), |c| match metrics_filename {
+                        Some(ref metrics_filename) => format!(
+                            "<a href='{}/{}'>{cid}</a> ",
+                            compile_id_dir.display(),
+                            metrics_filename,
+                            cid = c,
+                        ),
+                        None => format!("{c} "),
+                    });
+            if let Some(ref metrics_filename) = metrics_filename {
+                let mut key = e.compile_id.clone();
+                if let Some(c) = key.as_mut() {
+                    c.attempt = None;
+                }
+                metrics_page_index.insert(key, compile_id_dir.join(metrics_filename));
+            }
+            if let Some(ref features) = m.feature_usage {
+                let mut features: Vec<(&String, &bool)> = features.iter().collect();
+                features.sort();
+                for (feature, value) in features {
+                    record_feature_usage(&mut feature_usage_index, &e.compile_id, feature, *value);
+                }
+            }
+            let failure_compile_id = e
+                .compile_id
+                .as_ref()
+                .map_or("(unknown)".to_string(), |id| id.to_string());
             if let Some(rr) = m.restart_reasons.as_ref() {
                 for restart in rr {
+                    search_index.add(
+                        failure_compile_id.clone(),
+                        "restart".to_string(),
+                        "failures_and_restarts.html".to_string(),
+                        false,
+                        restart,
+                    );
                     breaks.failures.push((
                         id.clone(),
                         format!("{}", FailureReason::Restart(restart.clone())),
@@ -824,6 +2032,13 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
                     .clone()
                     .unwrap_or(String::from("N/A"));
                 let user_frame_lineno = m.fail_user_frame_lineno.unwrap_or(0);
+                search_index.add(
+                    failure_compile_id.clone(),
+                    "failure".to_string(),
+                    "failures_and_restarts.html".to_string(),
+                    false,
+                    &format!("{f}: {reason}\n{user_frame_filename}:{user_frame_lineno}"),
+                );
                 let failure_reason = FailureReason::Failure((
                     f.clone(),
                     reason.clone(),
@@ -842,10 +2057,33 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
                 }
             }
             metrics_index.entry(cid).or_default().push(m.clone());
+            attempt_metrics_index
+                .entry(e.compile_id.clone())
+                .or_default()
+                .push(m.clone());
+        }
+
+        if !config.export {
+            record_dynamic_shapes_event(&mut dynamic_shapes_index, &e);
+            if let Some(ref m) = e.compilation_metrics_runtime {
+                let mut key = e.compile_id.clone();
+                if let Some(c) = key.as_mut() {
+                    c.attempt = None;
+                }
+                runtime_metrics_index
+                    .entry(key)
+                    .or_default()
+                    .push(m.clone());
+                if let Some(us) = m.runtime_triton_autotune_time_us {
+                    autotune_compile(&mut autotuning_index, &e.compile_id)
+                        .runtime_autotune_time_s = format!("{:.3}", us as f64 / 1e6);
+                }
+            }
         }
 
         if config.export {
-            if let Some(ref guard) = e.guard_added {
+            let guard_added_enabled = config.parser_enabled("guard_added");
+            if let Some(guard) = e.guard_added.as_ref().filter(|_| guard_added_enabled) {
                 if guard.prefix.as_deref() != Some("eval") {
                     write_to_shortraw(&mut shortraw_content, None, &multi, &mut stats);
                     continue;
@@ -870,12 +2108,17 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
                     &multi,
                     &mut stats,
                     &tt,
+                    config.templates.get("main.css"),
                     &sym_expr_info_index,
                     &mut export_failures,
                 );
             }
 
-            if let Some(ref guard) = e.propagate_real_tensors_provenance {
+            if let Some(guard) = e
+                .propagate_real_tensors_provenance
+                .as_ref()
+                .filter(|_| guard_added_enabled)
+            {
                 let failure_type = "Data Dependent Error";
 
                 let reason = format!(
@@ -899,6 +2142,7 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
                     &multi,
                     &mut stats,
                     &tt,
+                    config.templates.get("main.css"),
                     &sym_expr_info_index,
                     &mut export_failures,
                 );
@@ -940,16 +2184,43 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
                     additional_info: additional_info.to_string(),
                 });
             }
+        } else if (e.guard_added.is_some() || e.propagate_real_tensors_provenance.is_some())
+            && config.parser_enabled("guard_added")
+        {
+            // Outside of export these are not failures, but the provenance of the expression
+            // is still worth a page in the compile's directory
+            let sym_expr_info_index_borrowed = sym_expr_info_index.borrow();
+            let parser: Box<dyn StructuredLogParser> =
+                Box::new(crate::parsers::PropagateRealTensorsParser {
+                    tt: &tt,
+                    css: config.templates.get("main.css"),
+                    sym_expr_info_index: &sym_expr_info_index_borrowed,
+                });
+            run_parser(
+                lineno,
+                &parser,
+                &e,
+                &payload,
+                &mut output_count,
+                &mut output,
+                compile_directory,
+                &multi,
+                &mut stats,
+            );
+        }
 
-            if let Some(sym_expr_info) = e.expression_created {
+        if let Some(sym_expr_info) = e.expression_created {
+            if let Some(result_id) = sym_expr_info.result_id {
                 sym_expr_info_index
                     .borrow_mut()
-                    .insert(sym_expr_info.result_id.unwrap(), sym_expr_info);
+                    .insert(result_id, sym_expr_info);
             }
+        }
 
-            if let Some(unbacked_symbol) = e.create_unbacked_symbol {
+        if let Some(unbacked_symbol) = e.create_unbacked_symbol {
+            if let Some(node_id) = unbacked_symbol.node_id {
                 sym_expr_info_index.borrow_mut().insert(
-                    unbacked_symbol.node_id.unwrap(),
+                    node_id,
                     SymExprInfoMetadata {
                         result: unbacked_symbol.symbol.clone(),
                         result_id: unbacked_symbol.node_id.clone(),
@@ -977,6 +2248,23 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
             }
         }
 
+        if e.describe_tensor.is_some()
+            || e.describe_storage.is_some()
+            || e.describe_source.is_some()
+        {
+            let mut index = tensor_description_index.borrow_mut();
+            let descriptions = index.entry(e.compile_id.clone()).or_default();
+            if let Some(tensor) = e.describe_tensor {
+                descriptions.tensors.push(tensor);
+            }
+            if let Some(storage) = e.describe_storage {
+                descriptions.storages.push(storage);
+            }
+            if let Some(source) = e.describe_source {
+                descriptions.sources.push(source);
+            }
+        }
+
         if let Some(specialization) = e.symbolic_shape_specialization {
             symbolic_shape_specialization_index
                 .borrow_mut()
@@ -992,6 +2280,132 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
                 .push(guard_added_fast)
         }
 
+        if !config.export {
+            let guards = if e.dynamo_guards.is_some() {
+                crate::parsers::normalize_dynamo_guards(&payload).unwrap_or_default()
+            } else if e.dynamo_cpp_guards_str.is_some() {
+                crate::parsers::normalize_cpp_guards(&payload)
+            } else {
+                Vec::new()
+            };
+            if !guards.is_empty() {
+                let guard_sets = guard_set_index.entry(compile_id_entry.clone()).or_default();
+                let guard_set = if e.dynamo_guards.is_some() {
+                    &mut guard_sets.dynamo_guards
+                } else {
+                    &mut guard_sets.cpp_guards
+                };
+                for guard in guards {
+                    if !guard_set.contains(&guard) {
+                        guard_set.push(guard);
+                    }
+                }
+            }
+            if e.inductor_output_code.is_some() {
+                let compile_id = e
+                    .compile_id
+                    .as_ref()
+                    .map_or("(unknown)".to_string(), |c| c.to_string());
+                let kernels = crate::parsers::extract_kernels(&payload);
+                if crate::parsers::is_fallback_output_code(&payload, &kernels) {
+                    fallback_compiles.push(FallbackCompile {
+                        compile_id: compile_id.clone(),
+                        extern_kernels: kernels.iter().map(|k| k.name.clone()).collect(),
+                    });
+                }
+                for kernel in kernels {
+                    let content_hash = kernel.source.as_ref().map_or(String::new(), |source| {
+                        num_kernel_definitions += 1;
+                        let mut hasher = Md5::new();
+                        hasher.update(source);
+                        format!("{:x}", hasher.finalize())
+                    });
+                    let key = if content_hash.is_empty() {
+                        kernel.name.clone()
+                    } else {
+                        content_hash.clone()
+                    };
+                    let entry =
+                        kernel_inventory
+                            .entry(key)
+                            .or_insert_with(|| KernelInventoryEntry {
+                                name: kernel.name,
+                                kind: kernel.kind,
+                                content_hash,
+                                compile_ids: Vec::new(),
+                                source_nodes: kernel.source_nodes,
+                                aten_nodes: kernel.aten_nodes,
+                                autotune: Vec::new(),
+                            });
+                    if !entry.compile_ids.contains(&compile_id) {
+                        entry.compile_ids.push(compile_id.clone());
+                    }
+                }
+            }
+            if let Some(ref artifact) = e.artifact {
+                if artifact.name == "recompile_reasons" {
+                    if let Ok(reasons) = serde_json::from_str::<Vec<String>>(&payload) {
+                        recompile_reasons_index.insert(compile_id_entry.clone(), reasons);
+                    }
+                }
+                if artifact.name == "triton_kernel_info" {
+                    if let Ok(infos) =
+                        serde_json::from_str::<FxHashMap<String, TritonKernelInfo>>(&payload)
+                    {
+                        let compile_id = e
+                            .compile_id
+                            .as_ref()
+                            .map_or("(unknown)".to_string(), |c| c.to_string());
+                        let compile = autotune_compile(&mut autotuning_index, &e.compile_id);
+                        for (name, info) in infos {
+                            let cache_state = info.autotune_cache_state.unwrap_or_default();
+                            let mut desc = cache_state.clone();
+                            if let Some(n) = info.num_configs {
+                                desc.push_str(&format!(", {} configs", n));
+                            }
+                            if let Some(us) = info.compile_time_us {
+                                desc.push_str(&format!(", {:.1} ms", us as f64 / 1000.0));
+                            }
+                            triton_kernel_info_index
+                                .insert((compile_id.clone(), name.clone()), desc);
+                            let time_us = info.compile_time_us.unwrap_or(0);
+                            compile.kernels.push(AutotunedKernel {
+                                name,
+                                cache_state,
+                                num_configs: info
+                                    .num_configs
+                                    .map_or("".to_string(), |n| n.to_string()),
+                                best_config: info
+                                    .best_config
+                                    .or(info.only_config)
+                                    .map_or("".to_string(), |c| format_triton_config(&c)),
+                                time_ms: format!("{:.1}", time_us as f64 / 1000.0),
+                                time_us,
+                            });
+                        }
+                    }
+                }
+                if let Some(caps) = cache_artifact_re.captures(&artifact.name) {
+                    if let Ok(m) = serde_json::from_str::<CacheArtifactMetadata>(&payload) {
+                        let from_hash_artifact = &caps[2] == "hash";
+                        cache_events.push(CacheEvent {
+                            compile_id: e.compile_id.clone(),
+                            layer: caps[1].to_string(),
+                            state: if from_hash_artifact {
+                                m.cache_state.unwrap_or_default()
+                            } else {
+                                caps[2].to_string()
+                            },
+                            from_hash_artifact,
+                            key: m.key,
+                            components: m.components.unwrap_or_default(),
+                            bypass_reason: m.cache_bypass_reason,
+                        });
+                    }
+                }
+            }
+        }
+
         if let Some(m) = e.dynamo_start {
             if let Some(mut stack) = m.stack {
                 maybe_remove_convert_frame_suffixes(&mut stack);
@@ -1010,9 +2424,18 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
                     // Only write payload file if no parser generated PayloadFile/PayloadReformatFile output and not a chromium event
                     if !payload.is_empty() && e.chromium_event.is_none() {
                         let hash_str = expect;
-                        let payload_path = PathBuf::from(format!("payloads/{}.txt", hash_str));
-                        output.push((payload_path, payload.clone()));
-                        Some(format!("payloads/{}.txt", hash_str))
+                        let payload_path = config.payloads_dir().join(format!("{hash_str}.txt"));
+                        let payload_url = payload_path.to_string_lossy().to_string();
+                        if payload_blobs.insert(payload_path.clone()) {
+                            match deferred {
+                                Some(ref mut deferred) => {
+                                    deferred
+                                        .insert(payload_path, DeferredOutput::Payload { lineno });
+                                }
+                                None => output.push((payload_path, payload.clone())),
+                            }
+                        }
+                        Some(payload_url)
                     } else {
                         None
                     }
@@ -1041,9 +2464,15 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
             .flatten()
             .find(|output_file| output_file.url.contains("exported_program"))
             .map(|output_file| output_file.url.clone());
+        add_directory(
+            &mut summary,
+            &directory,
+            &metrics_index,
+            &attempt_metrics_index,
+        );
 
         let index_context = ExportIndexContext {
-            css: EXPORT_CSS,
+            css: config.templates.get("export.css"),
             javascript: JAVASCRIPT,
             custom_header_html: config.custom_header_html.clone(),
             directory: directory
@@ -1062,13 +2491,210 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
             tt.render("index.html", &index_context)?,
         ));
 
-        return Ok(output);
+        return Ok((output, summary));
     }
 
     output.push((
         PathBuf::from("failures_and_restarts.html"),
         tt.render("failures_and_restarts.html", &breaks)?,
     ));
+
+    let guard_diffs = build_guard_diffs(&guard_set_index, &mut recompile_reasons_index);
+    let num_guard_diffs = if config.parser_enabled("guard_diff") {
+        guard_diffs.len()
+    } else {
+        0
+    };
+    if num_guard_diffs > 0 {
+        output.push((
+            PathBuf::from("guard_diff.html"),
+            tt.render(
+                "guard_diff.html",
+                &GuardDiffContext {
+                    css: config.templates.get("failures.css"),
+                    diffs: guard_diffs,
+                    qps: TEMPLATE_QUERY_PARAM_SCRIPT,
+                },
+            )?,
+        ));
+    }
+
+    let has_kernels = config.parser_enabled("kernels")
+        && (!kernel_inventory.is_empty() || !fallback_compiles.is_empty());
+    if has_kernels {
+        let kernels = kernel_inventory
+            .into_values()
+            .map(|mut kernel| {
+                kernel.autotune = kernel
+                    .compile_ids
+                    .iter()
+                    .filter_map(|cid| {
+                        triton_kernel_info_index
+                            .get(&(cid.clone(), kernel.name.clone()))
+                            .map(|desc| format!("{cid}: {desc}"))
+                    })
+                    .collect();
+                kernel
+            })
+            .collect();
+        output.push((
+            PathBuf::from("kernels.html"),
+            tt.render(
+                "kernels.html",
+                &KernelsContext {
+                    css: config.templates.get("failures.css"),
+                    kernels,
+                    num_definitions: num_kernel_definitions,
+                    fallback_compiles,
+                    qps: TEMPLATE_QUERY_PARAM_SCRIPT,
+                },
+            )?,
+        ));
+    }
+
+    // Runtime metrics are logged after the compile's metrics page was rendered, so render the
+    // page again with them
+    if !runtime_metrics_index.is_empty() {
+        let metrics_pages = metrics_pages.into_inner();
+        let positions: FxHashMap<&PathBuf, usize> = metrics_page_index
+            .values()
+            .filter_map(|path| Some((path, output.iter().position(|(p, _)| p == path)?)))
+            .collect();
+        for (compile_id, runtime_metrics) in &runtime_metrics_index {
+            let (Some(page), Some(pos)) = (
+                metrics_pages.get(compile_id),
+                metrics_page_index
+                    .get(compile_id)
+                    .and_then(|path| positions.get(path)),
+            ) else {
+                continue;
+            };
+            output[*pos].1 = crate::parsers::render_compilation_metrics(
+                &tt,
+                config.templates.get("main.css"),
+                page,
+                runtime_metrics,
+            )?;
+        }
+    }
+
+    let has_feature_usage =
+        config.parser_enabled("feature_usage") && !feature_usage_index.is_empty();
+    if has_feature_usage {
+        output.push((
+            PathBuf::from("feature_usage.html"),
+            tt.render(
+                "feature_usage.html",
+                &FeatureUsageContext {
+                    css: config.templates.get("failures.css"),
+                    features: feature_usage_index.into_values().collect(),
+                    qps: TEMPLATE_QUERY_PARAM_SCRIPT,
+                },
+            )?,
+        ));
+    }
+
+    if !config.export {
+        record_autotuning_events(&mut autotuning_index, &chromium_events);
+    }
+    // Every compile with Triton kernels logs their compile times, which alone don't make a report
+    let has_autotuning = config.parser_enabled("autotuning")
+        && autotuning_index.values().any(|c| {
+            c.num_benchmarks > 0
+                || !c.ops.is_empty()
+                || !c.autotune_time_s.is_empty()
+                || !c.runtime_autotune_time_s.is_empty()
+                || c.kernels.iter().any(|k| !k.cache_state.is_empty())
+        });
+    if has_autotuning {
+        output.push((
+            PathBuf::from("autotuning.html"),
+            tt.render(
+                "autotuning.html",
+                &autotuning_context(autotuning_index, config.templates.get("failures.css")),
+            )?,
+        ));
+    }
+
+    let has_cache_report = config.parser_enabled("cache_report") && !cache_events.is_empty();
+    if has_cache_report {
+        let (layers, bypass_reasons, misses) = build_cache_report(&cache_events);
+        output.push((
+            PathBuf::from("cache_report.html"),
+            tt.render(
+                "cache_report.html",
+                &CacheReportContext {
+                    css: config.templates.get("failures.css"),
+                    layers,
+                    bypass_reasons,
+                    misses,
+                    qps: TEMPLATE_QUERY_PARAM_SCRIPT,
+                },
+            )?,
+        ));
+    }
+
+    let has_dynamic_shapes = config.parser_enabled("dynamic_shapes")
+        && (!dynamic_shapes_index.symbols.is_empty()
+            || !dynamic_shapes_index.specializations.is_empty()
+            || !dynamic_shapes_index.guards.is_empty());
+    if has_dynamic_shapes {
+        let symbols: Vec<DynamicSymbolContext> =
+            dynamic_shapes_index.symbols.into_values().collect();
+        output.push((
+            PathBuf::from("dynamic_shapes.html"),
+            tt.render(
+                "dynamic_shapes.html",
+                &DynamicShapesContext {
+                    css: config.templates.get("failures.css"),
+                    num_unbacked: symbols.iter().filter(|s| s.unbacked).count(),
+                    symbols,
+                    specializations: dynamic_shapes_index.specializations,
+                    guards: dynamic_shapes_index.guards,
+                    qps: TEMPLATE_QUERY_PARAM_SCRIPT,
+                },
+            )?,
+        ));
+    }
+    let mut parser_outputs: Vec<(String, String)> = Vec::new();
+    let finish_context = crate::parsers::FinishContext {
+        directory: &directory,
+    };
+    for parser in default_parsers
+        .iter()
+        .chain(config.custom_parsers.iter())
+        .filter(|parser| config.parser_enabled(parser.name()))
+    {
+        match parser.finish(&finish_context) {
+            Ok(results) => {
+                for parser_result in results {
+                    match parser_result {
+                        ParserOutput::File(filename, out)
+                        | ParserOutput::GlobalFile(filename, out) => {
+                            let url = filename.to_string_lossy().to_string();
+                            parser_outputs.push((url.clone(), url));
+                            output.push((filename, out));
+                        }
+                        ParserOutput::Link(name, url) => {
+                            parser_outputs.push((name, url));
+                        }
+                        ParserOutput::PayloadFile(_) | ParserOutput::PayloadReformatFile(_, _) => {
+                            let err = crate::parsers::ParserError::InvalidOutput(
+                                "payload outputs aren't supported from finish".to_string(),
+                            )
+                            .into();
+                            record_parser_error(parser.name(), &err, &multi, &mut stats);
+                        }
+                    }
+                }
+            }
+            Err(err) => {
+                let err = err.context("finish failed");
+                record_parser_error(parser.name(), &err, &multi, &mut stats);
+            }
+        }
+    }
+
     pb.finish_with_message("done");
     spinner.finish();
 
@@ -1077,15 +2703,32 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
         serde_json::to_string_pretty(&chromium_events).unwrap(),
     ));
 
+    // Guards that aren't valid JSON have their own counter, apart from other parser failures
+    stats.fail_dynamo_guards_json = stats
+        .parser_errors
+        .get(&("dynamo_guards".to_string(), "payload_decode".to_string()))
+        .copied()
+        .unwrap_or_default();
+    stats.fail_parser -= stats.fail_dynamo_guards_json;
     eprintln!("{}", stats);
-    if unknown_fields.len() > 0 {
+    output.push((
+        PathBuf::from("parse_stats.json"),
+        serde_json::to_string_pretty(&stats)?,
+    ));
+    if !unknown_fields.is_empty() {
         eprintln!(
-            "Unknown fields: {:?} (consider updating tlparse to render these)",
-            unknown_fields
+            "Rendered unknown fields with the fallback renderer: {:?}",
+            unknown_fields.keys().collect::<Vec<_>>()
         );
     }
 
     let has_unknown_compile_id = directory.contains_key(&None);
+    // Highlighted views of code outputs, which provenance tracking must not pick up
+    let readable_paths: FxHashSet<PathBuf> = directory
+        .values()
+        .flatten()
+        .filter_map(|f| f.readable_url.as_ref().map(PathBuf::from))
+        .collect();
 
     let directory_names: Vec<String> = directory
         .iter()
@@ -1094,17 +2737,72 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
                 .map_or("(unknown)".to_string(), |e| e.as_directory_name())
         })
         .collect();
+    if config.content_addressed {
+        // Payload dumps were hashed as they were stored; hash the rest for compile_directory.json
+        let contents: FxHashMap<&Path, &String> =
+            output.iter().map(|(p, c)| (p.as_path(), c)).collect();
+        for file in directory.values_mut().flatten() {
+            if file.hash.is_none() {
+                file.hash = contents
+                    .get(Path::new(&file.url))
+                    .map(|c| format!("{:x}", Md5::digest(c.as_bytes())));
+            }
+        }
+    }
     output.push((
         PathBuf::from("compile_directory.json"),
         serde_json::to_string_pretty(&directory_to_json(&directory))?,
     ));
+    output.push((PathBuf::from("search_index.js"), search_index.to_js()?));
+    let mut cache_status: FxHashMap<&Option<CompileId>, (&'static str, u8)> = FxHashMap::default();
+    for event in &cache_events {
+        let status = match event.state.as_str() {
+            "miss" => ("miss", 3),
+            "hit" => ("hit", 2),
+            "bypass" => ("bypass", 1),
+            _ => continue,
+        };
+        let entry = cache_status.entry(&event.compile_id).or_insert(status);
+        if status.1 > entry.1 {
+            *entry = status;
+        }
+    }
+    let rank = expected_rank.flatten();
+    add_directory(
+        &mut summary,
+        &directory,
+        &metrics_index,
+        &attempt_metrics_index,
+    );
+    let summaries: Vec<CompileIdSummary> = directory
+        .keys()
+        .map(|compile_id| {
+            let (metrics, status) =
+                attempt_metrics(compile_id, &metrics_index, &attempt_metrics_index);
+            CompileIdSummary::new(
+                metrics,
+                status,
+                cache_status.get(compile_id).map(|(status, _)| *status),
+                rank,
+            )
+        })
+        .collect();
     let index_context = IndexContext {
-        css: CSS,
+        css: config.templates.get("main.css"),
         javascript: JAVASCRIPT,
+        search_js: SEARCH_JS,
+        compile_ids_js: COMPILE_IDS_JS,
         custom_header_html: config.custom_header_html.clone(),
         directory: directory
             .drain(..)
-            .map(|(x, y)| (x.map_or("(unknown)".to_string(), |e| e.to_string()), y))
+            .zip(summaries)
+            .map(|((x, y), summary)| {
+                (
+                    x.map_or("(unknown)".to_string(), |e| e.to_string()),
+                    group_output_files(y),
+                    summary,
+                )
+            })
             .collect(),
         stack_trie_html: stack_trie
             .fmt(Some(&metrics_index), "Stack", false)
@@ -1118,13 +2816,31 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
         qps: TEMPLATE_QUERY_PARAM_SCRIPT,
         has_inductor_provenance: config.inductor_provenance,
         directory_names: directory_names.clone(),
+        num_guard_diffs,
+        has_dynamic_shapes,
+        has_cache_report,
+        has_kernels,
+        has_autotuning,
+        has_feature_usage,
+        unknown_fields: unknown_fields.into_iter().collect(),
+        parser_outputs,
+        parser_errors: stats
+            .parser_errors
+            .iter()
+            .map(|((parser, kind), count)| (parser.clone(), kind.clone(), *count))
+            .collect(),
     };
     output.push((
         PathBuf::from("index.html"),
         tt.render("index.html", &index_context)?,
     ));
 
-    output.push((PathBuf::from("raw.log"), fs::read_to_string(path)?));
+    match deferred {
+        Some(ref mut deferred) => {
+            deferred.insert(PathBuf::from("raw.log"), DeferredOutput::RawLog);
+        }
+        None => output.push((PathBuf::from("raw.log"), fs::read_to_string(path)?)),
+    }
 
     // Create string table from INTERN_TABLE as an array with nulls for missing indices
     let intern_table = INTERN_TABLE.lock().unwrap();
@@ -1173,17 +2889,38 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
         // Helper function to get file content for a specific directory name
         fn get_file_content(
             output: &[(PathBuf, String)],
+            provenance_inputs: &[(PathBuf, String)],
+            readable_paths: &FxHashSet<PathBuf>,
+            payload_aliases: &[(PathBuf, PathBuf)],
             filename_patterns: &[&str],
             directory_name: &str,
         ) -> String {
             // Try each pattern in order and return the first match found
             for pattern in filename_patterns {
-                if let Some((_, content)) = output.iter().rev().find(|(path, _)| {
+                let matches = |path: &PathBuf| {
                     path.to_string_lossy()
                         .contains(&format!("{}/{}", directory_name, pattern))
-                }) {
+                };
+                if let Some((_, content)) = output
+                    .iter()
+                    .chain(provenance_inputs)
+                    .rev()
+                    .find(|(path, _)| !readable_paths.contains(path) && matches(path))
+                {
                     return content.clone();
                 }
+                // Content addressed payloads only exist under their hash
+                if let Some((_, blob)) =
+                    payload_aliases.iter().rev().find(|(path, _)| matches(path))
+                {
+                    if let Some((_, content)) = output
+                        .iter()
+                        .chain(provenance_inputs)
+                        .find(|(path, _)| path == blob)
+                    {
+                        return content.clone();
+                    }
+                }
             }
             String::default()
         }
@@ -1192,20 +2929,41 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
         for directory_name in &directory_names {
             let pre_grad_graph_content = get_file_content(
                 &output,
+                &provenance_inputs,
+                &readable_paths,
+                &payload_aliases,
                 &["before_pre_grad_graph", "inductor_pre_grad_graph"],
                 directory_name,
             );
             let post_grad_graph_content = get_file_content(
                 &output,
+                &provenance_inputs,
+                &readable_paths,
+                &payload_aliases,
                 &["after_post_grad_graph", "inductor_post_grad_graph"],
                 directory_name,
             );
-            let output_code_content =
-                get_file_content(&output, &["inductor_output_code"], directory_name);
-            let aot_code_content =
-                get_file_content(&output, &["inductor_aot_wrapper_code"], directory_name);
+            let output_code_content = get_file_content(
+                &output,
+                &provenance_inputs,
+                &readable_paths,
+                &payload_aliases,
+                &["inductor_output_code"],
+                directory_name,
+            );
+            let aot_code_content = get_file_content(
+                &output,
+                &provenance_inputs,
+                &readable_paths,
+                &payload_aliases,
+                &["inductor_aot_wrapper_code"],
+                directory_name,
+            );
             let node_mappings_content = get_file_content(
                 &output,
+                &provenance_inputs,
+                &readable_paths,
+                &payload_aliases,
                 &["inductor_provenance_tracking_node_mappings"],
                 directory_name,
             );
@@ -1226,7 +2984,7 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
                 tt.render(
                     "provenance_tracking.html",
                     &ProvenanceContext {
-                        css: PROVENANCE_CSS,
+                        css: config.templates.get("provenance.css"),
                         js: PROVENANCE_JS,
                         pre_grad_graph_content,
                         post_grad_graph_content,
@@ -1239,7 +2997,176 @@ pub fn parse_path(path: &PathBuf, config: &ParseConfig) -> anyhow::Result<ParseO
         }
     }
 
-    Ok(output)
+    summary.stack_trie = stack_trie;
+    Ok((output, summary))
+}
+
+/// Recover compile ids, artifacts (with their contents), compilation metrics and failures from
+/// the output of `parse_path`, via `compile_directory.json` and `raw.jsonl`.
+pub fn parse_summary(output: &ParseOutput) -> anyhow::Result<ParseSummary> {
+    let contents: FxHashMap<&Path, &str> = output
+        .iter()
+        .map(|(path, content)| (path.as_path(), content.as_str()))
+        .collect();
+    let read = |name: &str| {
+        contents
+            .get(Path::new(name))
+            .copied()
+            .ok_or_else(|| anyhow!("{name} missing from parse output"))
+    };
+
+    let mut summary = ParseSummary::default();
+    let directory: FxIndexMap<String, CompileDirectoryEntry> =
+        serde_json::from_str(read("compile_directory.json")?)?;
+    for (compile_id, entry) in directory {
+        for mut artifact in entry.artifacts {
+            artifact.compile_id = compile_id.clone();
+            artifact.content = contents
+                .get(Path::new(&artifact.url))
+                .map(|c| c.to_string());
+            summary.artifacts.push(artifact);
+        }
+        summary.compile_ids.push(compile_id);
+    }
+
+    for line in read("raw.jsonl")?.lines() {
+        add_compilation_metrics(&mut summary, line)?;
+    }
+    Ok(summary)
+}
+
+// Adds the compilation metrics of an envelope to `summary`, with the failures and restarts they
+// record; other envelopes are ignored
+fn add_compilation_metrics(summary: &mut ParseSummary, envelope: &str) -> anyhow::Result<()> {
+    let Ok(Value::Object(mut envelope)) = serde_json::from_str::<Value>(envelope) else {
+        return Ok(());
+    };
+    let Some(Value::Object(mut metrics)) = envelope.remove("compilation_metrics") else {
+        return Ok(());
+    };
+    let compile_id: CompileId = serde_json::from_value(Value::Object(envelope))?;
+    let compile_id = compile_id.to_string();
+    let m: CompilationMetricsMetadata = serde_json::from_value(Value::Object(metrics.clone()))?;
+    for restart in m.restart_reasons.unwrap_or_default() {
+        summary.failures.push(FailureRecord {
+            compile_id: compile_id.clone(),
+            failure_type: "RestartAnalysis".to_string(),
+            reason: restart,
+            user_frame: None,
+        });
+    }
+    if let Some(failure_type) = m.fail_type {
+        summary.failures.push(FailureRecord {
+            compile_id: compile_id.clone(),
+            failure_type,
+            reason: m.fail_reason.unwrap_or_default(),
+            user_frame: m
+                .fail_user_frame_filename
+                .map(|filename| format!("{filename}:{}", m.fail_user_frame_lineno.unwrap_or(0))),
+        });
+    }
+    metrics.insert("compile_id".to_string(), Value::String(compile_id));
+    summary.metrics.push(Value::Object(metrics));
+    Ok(())
+}
+
+// Adds the compile ids and files of the index to `summary`, as compile_directory.json lists them
+fn add_directory(
+    summary: &mut ParseSummary,
+    directory: &FxIndexMap<Option<CompileId>, Vec<OutputFile>>,
+    metrics_index: &CompilationMetricsIndex,
+    attempt_metrics_index: &CompilationMetricsIndex,
+) {
+    for (compile_id, files) in directory {
+        let (_, status) = attempt_metrics(compile_id, metrics_index, attempt_metrics_index);
+        let compile_id = compile_id
+            .as_ref()
+            .map_or_else(|| "unknown".to_string(), |cid| cid.to_string());
+        summary.artifacts.extend(files.iter().map(|file| {
+            ArtifactRecord {
+                compile_id: compile_id.clone(),
+                name: file
+                    .name
+                    .split('/')
+                    .next_back()
+                    .unwrap_or(&file.name)
+                    .to_string(),
+                number: file.number as usize,
+                url: file.url.clone(),
+                hash: file.hash.clone(),
+                content: None,
+            }
+        }));
+        summary.files.insert(compile_id.clone(), files.clone());
+        summary.statuses.insert(compile_id.clone(), status);
+        summary.compile_ids.push(compile_id);
+    }
+}
+
+// Metrics are indexed by frame compile, without the attempt
+fn metrics_key(compile_id: &Option<CompileId>) -> Option<CompileId> {
+    let mut metrics_id = compile_id.clone();
+    if let Some(c) = metrics_id.as_mut() {
+        if c.frame_compile_id.is_some() {
+            c.attempt = Some(0);
+        }
+    }
+    metrics_id
+}
+
+// The metrics and status of one attempt of a frame compile.  Dynamo logs a frame compile's metrics
+// once, with the attempt that finished: the attempts before it were restarted, which is what the
+// restart_reasons of those metrics describe.
+fn attempt_metrics<'m>(
+    compile_id: &Option<CompileId>,
+    metrics_index: &'m CompilationMetricsIndex,
+    attempt_metrics_index: &'m CompilationMetricsIndex,
+) -> (Option<&'m Vec<CompilationMetricsMetadata>>, CompileStatus) {
+    let frame_metrics = metrics_index.get(&metrics_key(compile_id));
+    if compile_id.as_ref().is_none_or(|c| c.attempt.is_none()) {
+        // Without attempts in the log, the frame compile's metrics are the compile id's own
+        return (frame_metrics, CompileStatus::of(frame_metrics));
+    }
+    match attempt_metrics_index.get(compile_id) {
+        Some(metrics) => {
+            let status = match CompileStatus::of(Some(metrics)) {
+                CompileStatus::Break => CompileStatus::Ok,
+                status => status,
+            };
+            (Some(metrics), status)
+        }
+        None if frame_metrics.is_some() => (None, CompileStatus::Break),
+        None => (None, CompileStatus::Missing),
+    }
+}
+
+/// Bundle the output of `parse_path` into one self-contained HTML page, which embeds every file
+/// gzipped and base64'd and decodes them in the browser as they are viewed.
+pub fn bundle_single_file(output: &ParseOutput, title: &str) -> anyhow::Result<String> {
+    use base64::Engine;
+    use std::io::Write;
+
+    let mut files = serde_json::Map::new();
+    for (path, content) in output {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(content.as_bytes())?;
+        let encoded = base64::engine::general_purpose::STANDARD.encode(encoder.finish()?);
+        files.insert(path.to_string_lossy().to_string(), Value::String(encoded));
+    }
+    // The files live in a <script> element, which mustn't see a closing tag
+    let files = serde_json::to_string(&files)?.replace("</", "<\\/");
+
+    let mut tt = TinyTemplate::new();
+    tt.add_formatter("format_unescaped", tinytemplate::format_unescaped);
+    tt.add_template("single_file.html", TEMPLATE_SINGLE_FILE)?;
+    Ok(tt.render(
+        "single_file.html",
+        &SingleFileContext {
+            title: title.to_string(),
+            files,
+            js: SINGLE_FILE_JS,
+        },
+    )?)
 }
 
 pub fn read_chromium_events_with_pid(
@@ -1279,10 +3206,13 @@ pub fn generate_multi_rank_html(
     // Create the TinyTemplate instance for rendering the landing page.
     let mut tt = TinyTemplate::new();
     tt.add_formatter("format_unescaped", tinytemplate::format_unescaped);
-    tt.add_template("multi_rank_index.html", TEMPLATE_MULTI_RANK_INDEX)?;
+    tt.add_template(
+        "multi_rank_index.html",
+        cfg.templates.get("multi_rank_index.html"),
+    )?;
 
     let ctx = MultiRankContext {
-        css: CSS,
+        css: cfg.templates.get("main.css"),
         custom_header_html: &cfg.custom_header_html,
         num_ranks: sorted_ranks.len(),
         ranks: sorted_ranks,
@@ -0,0 +1,3 @@
+fn main() -> anyhow::Result<()> {
+    tlparse::cli::run(std::env::args_os())
+}
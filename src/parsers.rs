@@ -1,9 +1,13 @@
+use crate::config::{find_rule, RenderRule, Renderer};
 use crate::templates::TEMPLATE_QUERY_PARAM_SCRIPT;
 use crate::{types::*, ParseConfig};
 use html_escape::encode_text;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use std::cell::RefCell;
 use std::collections::HashSet;
 use std::ffi::{OsStr, OsString};
+use std::fmt;
 use std::path::Path;
 use std::path::PathBuf;
 use tinytemplate::TinyTemplate;
@@ -20,11 +24,15 @@ fn format_json_pretty(payload: &str) -> Result<String, anyhow::Error> {
     }
 }
 
-use syntect::highlighting::ThemeSet;
-use syntect::parsing::SyntaxSet;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::html::{styled_line_to_highlighted_html, IncludeBackground};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
 
 // Re-export types from types.rs for external use
-pub use crate::types::{CompileId, EmptyMetadata, Envelope, GraphRuntime, Metadata, OpRuntime};
+pub use crate::types::{
+    CompileId, EmptyMetadata, Envelope, FxIndexMap, GraphRuntime, Metadata, OpRuntime, OutputFile,
+};
 
 pub enum ParserOutput {
     File(PathBuf, String),       // File to be saved on disk
@@ -37,6 +45,69 @@ pub enum ParserOutput {
 // Each parser returns a list of files to save and links to render in compile directory
 pub type ParserResults = Vec<ParserOutput>;
 
+/**
+ * Why a parser failed.  parse returns anyhow::Result, so parsers return these with `.into()`
+ * (or `?`) and parse_path recovers the kind to count failures per parser and kind.
+ */
+#[derive(Debug)]
+pub enum ParserError {
+    // The payload isn't in the expected format, e.g. malformed JSON
+    PayloadDecode(String),
+    // The parser was handed metadata it doesn't understand
+    SchemaMismatch(String),
+    // Rendering the output (templates, syntax highlighting) failed
+    TemplateRender(String),
+    UnsupportedEncoding(String),
+    // The parser returned an output it isn't allowed to, e.g. a payload file from finish
+    InvalidOutput(String),
+    // Categories defined by custom parsers
+    Custom { kind: String, message: String },
+}
+
+impl ParserError {
+    pub fn kind(&self) -> &str {
+        match self {
+            ParserError::PayloadDecode(_) => "payload_decode",
+            ParserError::SchemaMismatch(_) => "schema_mismatch",
+            ParserError::TemplateRender(_) => "template_render",
+            ParserError::UnsupportedEncoding(_) => "unsupported_encoding",
+            ParserError::InvalidOutput(_) => "invalid_output",
+            ParserError::Custom { kind, .. } => kind,
+        }
+    }
+
+    // The kind of an error returned from parse, including errors from common libraries
+    // that were propagated with `?`
+    pub fn kind_of(err: &anyhow::Error) -> String {
+        if let Some(e) = err.downcast_ref::<ParserError>() {
+            e.kind().to_string()
+        } else if err.downcast_ref::<serde_json::Error>().is_some() {
+            "payload_decode".to_string()
+        } else if err.downcast_ref::<tinytemplate::error::Error>().is_some() {
+            "template_render".to_string()
+        } else {
+            "other".to_string()
+        }
+    }
+}
+
+impl fmt::Display for ParserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParserError::PayloadDecode(msg) => write!(f, "Failed to decode payload: {msg}"),
+            ParserError::SchemaMismatch(msg) => write!(f, "{msg}"),
+            ParserError::TemplateRender(msg) => write!(f, "Failed to render: {msg}"),
+            ParserError::UnsupportedEncoding(encoding) => {
+                write!(f, "Unsupported encoding: {encoding}")
+            }
+            ParserError::InvalidOutput(msg) => write!(f, "Invalid output: {msg}"),
+            ParserError::Custom { kind, message } => write!(f, "{kind}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ParserError {}
+
 /**
  * StructuredLogParser
  * Parses a structured log and returns a vec of file outputs.
@@ -59,12 +130,36 @@ pub trait StructuredLogParser {
         payload: &str,                  // Payload from the log (empty string when None)
     ) -> anyhow::Result<ParserResults>;
 
-    // Name of the parser, for error logging
+    // Name of the parser, for error logging and --only-parsers / --skip-parsers
     fn name(&self) -> &'static str;
+
+    // Envelope keys this parser consumes, for `tlparse parsers`. Most parsers are named
+    // after the key they read.
+    fn envelope_keys(&self) -> Vec<String> {
+        vec![self.name().to_string()]
+    }
+
+    // Called once after the last log line, for run-wide outputs such as aggregate
+    // pages. Files are written as-is (no unique suffix) and linked from index.html.
+    // Like parse, finish takes &self, so parsers that accumulate state across envelopes
+    // should keep it in a RefCell and take it back out here.
+    fn finish(&self, _ctx: &FinishContext) -> anyhow::Result<ParserResults> {
+        Ok(Vec::new())
+    }
+}
+
+// Read-only view of the run passed to StructuredLogParser::finish
+pub struct FinishContext<'a> {
+    // Files emitted so far, per compile id, in the order they were produced
+    pub directory: &'a FxIndexMap<Option<CompileId>, Vec<OutputFile>>,
 }
 
 // Helper function to build file path with compile ID directory
-fn build_file_path(filename: &str, lineno: usize, compile_id: &Option<CompileId>) -> PathBuf {
+pub(crate) fn build_file_path(
+    filename: &str,
+    lineno: usize,
+    compile_id: &Option<CompileId>,
+) -> PathBuf {
     let compile_id_dir: PathBuf = compile_id
         .as_ref()
         .map_or(format!("unknown_{lineno}"), |cid| cid.as_directory_name())
@@ -84,6 +179,38 @@ fn simple_file_output(
     Ok(Vec::from([ParserOutput::File(f, String::from(payload))]))
 }
 
+// Fallback renderer for envelope keys that no parser knows about (they end up in
+// Envelope._other).  Dumps the metadata and payload so new log types are visible
+// before anyone writes a dedicated parser for them.
+pub fn render_unknown_envelope(
+    key: &str,
+    metadata: &Value,
+    lineno: usize,
+    compile_id: &Option<CompileId>,
+    payload: &str,
+) -> anyhow::Result<ParserResults> {
+    let (filename, content) = if payload.is_empty() {
+        (
+            format!("unknown_{key}.json"),
+            serde_json::to_string_pretty(metadata)?,
+        )
+    } else if let Ok(payload_json) = serde_json::from_str::<Value>(payload) {
+        (
+            format!("unknown_{key}.json"),
+            serde_json::to_string_pretty(&serde_json::json!({
+                "metadata": metadata,
+                "payload": payload_json,
+            }))?,
+        )
+    } else {
+        (
+            format!("unknown_{key}.txt"),
+            format!("{}\n\n{}", serde_json::to_string_pretty(metadata)?, payload),
+        )
+    };
+    simple_file_output(&filename, lineno, compile_id, &content)
+}
+
 // Takes a filename and returns PayloadFile output that uses payload directly from log entry
 fn payload_file_output(
     filename: &str,
@@ -169,7 +296,7 @@ impl StructuredLogParser for GraphDumpParser {
             };
             payload_file_output(&filename.to_string_lossy(), lineno, compile_id)
         } else {
-            Err(anyhow::anyhow!("Expected GraphDump metadata"))
+            Err(ParserError::SchemaMismatch("Expected GraphDump metadata".to_string()).into())
         }
     }
 }
@@ -226,6 +353,243 @@ impl StructuredLogParser for DynamoGuardParser<'_> {
     }
 }
 
+/// Normalizes a `dynamo_guards` payload (a JSON list of guards) into a guard set
+pub fn normalize_dynamo_guards(payload: &str) -> anyhow::Result<Vec<NormalizedGuard>> {
+    let guards = serde_json::from_str::<Vec<DynamoGuard>>(payload)?;
+    Ok(guards
+        .iter()
+        .map(|g| NormalizedGuard::new("GUARD", &g.code))
+        .collect())
+}
+
+/// Normalizes a `dynamo_cpp_guards_str` payload (the TREE_GUARD_MANAGER dump) into a guard set.
+/// Guard managers only describe structure, so only their leaf guards are kept, and the trailing
+/// `# file.py:line in fn` comments are dropped since they don't affect what is checked.
+pub fn normalize_cpp_guards(payload: &str) -> Vec<NormalizedGuard> {
+    static GUARD_RE: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"^[|\s]*\+-\s*([A-Za-z_]+):\s*(.*)$").unwrap());
+    static COMMENT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s{2,}#\s.*$").unwrap());
+    payload
+        .lines()
+        .filter_map(|line| {
+            let caps = GUARD_RE.captures(line)?;
+            let kind = caps.get(1)?.as_str();
+            if kind.ends_with("Manager") {
+                return None;
+            }
+            let code = COMMENT_RE.replace(caps.get(2)?.as_str(), "");
+            Some(NormalizedGuard::new(kind, &code))
+        })
+        .collect()
+}
+
+/// Diffs the guard sets of two consecutive compiles of a frame.  Guards that were removed and
+/// added with the same subject (e.g. a `check_tensor` on the same input) are reported as changed.
+pub fn diff_guard_sets(
+    prev: &[NormalizedGuard],
+    next: &[NormalizedGuard],
+) -> (
+    Vec<NormalizedGuard>,
+    Vec<NormalizedGuard>,
+    Vec<ChangedGuard>,
+    usize,
+) {
+    let prev_set: HashSet<&NormalizedGuard> = prev.iter().collect();
+    let next_set: HashSet<&NormalizedGuard> = next.iter().collect();
+    let mut removed: Vec<NormalizedGuard> = prev
+        .iter()
+        .filter(|g| !next_set.contains(g))
+        .cloned()
+        .collect();
+    let mut added: Vec<NormalizedGuard> = next
+        .iter()
+        .filter(|g| !prev_set.contains(g))
+        .cloned()
+        .collect();
+    let num_unchanged = next.len() - added.len();
+
+    let mut changed = Vec::new();
+    removed.retain(|r| {
+        let subject = r.subject();
+        if let Some(pos) = added.iter().position(|a| a.subject() == subject) {
+            let a = added.remove(pos);
+            changed.push(ChangedGuard {
+                kind: a.kind,
+                before: r.code.clone(),
+                after: a.code,
+            });
+            false
+        } else {
+            true
+        }
+    });
+    (added, removed, changed, num_unchanged)
+}
+
+/// Diffs the cache key components of two cache lookups.  Components are logged as
+/// `[hash] name: value`; a component is reported when its hash differs, or when it only exists
+/// on one side.  Long values are elided to their hash.
+pub fn diff_cache_components(prev: &[String], next: &[String]) -> Vec<ChangedCacheComponent> {
+    fn split(component: &str) -> (&str, &str, &str) {
+        let (hash, rest) = component
+            .strip_prefix('[')
+            .and_then(|c| c.split_once("] "))
+            .unwrap_or(("", component));
+        let (name, value) = rest.split_once(": ").unwrap_or((rest, ""));
+        (name, hash, value)
+    }
+    fn describe(hash: &str, value: &str) -> String {
+        if value.len() <= 120 && !value.contains('\n') {
+            value.to_string()
+        } else {
+            format!("[{}]", hash)
+        }
+    }
+    let prev_by_name: FxIndexMap<&str, (&str, &str)> = prev
+        .iter()
+        .map(|c| {
+            let (name, hash, value) = split(c);
+            (name, (hash, value))
+        })
+        .collect();
+    let next_by_name: FxIndexMap<&str, (&str, &str)> = next
+        .iter()
+        .map(|c| {
+            let (name, hash, value) = split(c);
+            (name, (hash, value))
+        })
+        .collect();
+
+    let mut changed = Vec::new();
+    for (name, (hash, value)) in &next_by_name {
+        match prev_by_name.get(name) {
+            Some((prev_hash, _)) if prev_hash == hash => {}
+            Some((prev_hash, prev_value)) => changed.push(ChangedCacheComponent {
+                name: name.to_string(),
+                before: describe(prev_hash, prev_value),
+                after: describe(hash, value),
+            }),
+            None => changed.push(ChangedCacheComponent {
+                name: name.to_string(),
+                before: "(absent)".to_string(),
+                after: describe(hash, value),
+            }),
+        }
+    }
+    for (name, (hash, value)) in &prev_by_name {
+        if !next_by_name.contains_key(name) {
+            changed.push(ChangedCacheComponent {
+                name: name.to_string(),
+                before: describe(hash, value),
+                after: "(absent)".to_string(),
+            });
+        }
+    }
+    changed
+}
+
+/// Extracts the Triton kernels defined in an `inductor_output_code` payload, along with the
+/// source graph nodes from the comment Inductor emits above each definition, followed by the
+/// extern kernels called from the generated `call` function.
+pub fn extract_kernels(code: &str) -> Vec<ExtractedKernel> {
+    static DEF_RE: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"^\w+ = async_compile\.triton\('(\w+)', '''").unwrap());
+    static NODES_RE: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"^# Topologically Sorted Source Nodes: \[(.*)\], Original ATen: \[(.*)\]$")
+            .unwrap()
+    });
+    static HEURISTIC_RE: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"^@triton_heuristics\.(\w+)\(").unwrap());
+    static EXTERN_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bextern_kernels\.(\w+)\(").unwrap());
+
+    let split_nodes = |s: &str| -> Vec<String> {
+        s.split(", ")
+            .filter(|n| !n.is_empty())
+            .map(|n| n.to_string())
+            .collect()
+    };
+
+    let lines: Vec<&str> = code.lines().collect();
+    let mut kernels = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let Some(caps) = DEF_RE.captures(lines[i]) else {
+            i += 1;
+            continue;
+        };
+        let name = caps[1].to_string();
+        let (mut source_nodes, mut aten_nodes) = (Vec::new(), Vec::new());
+        for comment in lines[..i].iter().rev().take_while(|l| l.starts_with('#')) {
+            if let Some(nodes) = NODES_RE.captures(comment) {
+                source_nodes = split_nodes(&nodes[1]);
+                aten_nodes = split_nodes(&nodes[2]);
+                break;
+            }
+        }
+        let start = i + 1;
+        i = start;
+        while i < lines.len() && !lines[i].starts_with("'''") {
+            i += 1;
+        }
+        let body = &lines[start..i];
+        let kind = body
+            .iter()
+            .find_map(|l| {
+                HEURISTIC_RE.captures(l).map(|c| match &c[1] {
+                    h if h.ends_with("reduction") => "reduction".to_string(),
+                    h => h.to_string(),
+                })
+            })
+            .unwrap_or_else(|| {
+                match name.split('_').nth(1) {
+                    Some("poi") => "pointwise",
+                    Some("red") | Some("per") => "reduction",
+                    Some("tem") => "template",
+                    _ => "unknown",
+                }
+                .to_string()
+            });
+        kernels.push(ExtractedKernel {
+            name,
+            kind,
+            source_nodes,
+            aten_nodes,
+            source: Some(body.join("\n")),
+        });
+    }
+
+    let mut externs: Vec<String> = Vec::new();
+    for caps in EXTERN_RE.captures_iter(code) {
+        let name = format!("extern_kernels.{}", &caps[1]);
+        if !externs.contains(&name) {
+            externs.push(name);
+        }
+    }
+    kernels.extend(externs.into_iter().map(|name| ExtractedKernel {
+        name,
+        kind: "extern".to_string(),
+        source_nodes: Vec::new(),
+        aten_nodes: Vec::new(),
+        source: None,
+    }));
+    kernels
+}
+
+/// Whether an `inductor_output_code` payload fell back entirely: it defines no Triton kernels
+/// and calls extern or ATen fallback kernels instead.  Output code of the C++ backends (CPU
+/// and AOTInductor wrappers) never defines Triton kernels, so it is left alone.
+pub fn is_fallback_output_code(code: &str, kernels: &[ExtractedKernel]) -> bool {
+    static CPP_RE: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?m)\basync_compile\.cpp(_pybinding)?\(|^#include\b").unwrap());
+    static ATEN_FALLBACK_RE: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"\btorch\.ops\.aten\.\w+\.\w+\(").unwrap());
+
+    if kernels.iter().any(|k| k.source.is_some()) || CPP_RE.is_match(code) {
+        return false;
+    }
+    kernels.iter().any(|k| k.kind == "extern") || ATEN_FALLBACK_RE.is_match(code)
+}
+
 pub struct InductorOutputCodeParser {
     // If true we output the code as plain text, otherwise we output it as rendered html
     plain_text: bool,
@@ -288,7 +652,10 @@ impl StructuredLogParser for InductorOutputCodeParser {
                 let output_content = match generate_html_output(payload) {
                     Ok(html) => html,
                     Err(_e) => {
-                        return Err(anyhow::anyhow!("Failed to parse inductor code to html"))
+                        return Err(ParserError::TemplateRender(
+                            "Failed to parse inductor code to html".to_string(),
+                        )
+                        .into())
                     }
                 };
                 simple_file_output(
@@ -299,22 +666,152 @@ impl StructuredLogParser for InductorOutputCodeParser {
                 )
             }
         } else {
-            Err(anyhow::anyhow!("Expected InductorOutputCode metadata"))
+            Err(
+                ParserError::SchemaMismatch("Expected InductorOutputCode metadata".to_string())
+                    .into(),
+            )
         }
     }
 }
 
 fn generate_html_output(payload: &str) -> Result<String, anyhow::Error> {
-    let syntax_set = SyntaxSet::load_defaults_newlines();
-    let theme_set = ThemeSet::load_defaults();
-    let syntax = syntax_set.find_syntax_by_extension("py").unwrap();
-    let html = syntect::html::highlighted_html_for_string(
-        &payload,
-        &syntax_set,
-        &syntax,
-        &theme_set.themes["InspiredGitHub"],
-    );
-    Ok(html?)
+    highlight_html(payload, CodeLanguage::Python)
+}
+
+/// Languages the shared highlighter colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeLanguage {
+    // Also Triton kernels and FX graphs, which print as Python
+    Python,
+    Cpp,
+}
+
+impl CodeLanguage {
+    fn extension(self) -> &'static str {
+        match self {
+            CodeLanguage::Python => "py",
+            CodeLanguage::Cpp => "cpp",
+        }
+    }
+
+    /// Language of the code an envelope carries as its payload, if it carries code
+    pub fn of_envelope(e: &Envelope) -> Option<Self> {
+        if let Some(ref artifact) = e.artifact {
+            return Self::of_name(&artifact.name);
+        }
+        if let Some(ref dump) = e.graph_dump {
+            if dump.dump_type.as_deref() == Some("cpp") {
+                return Some(CodeLanguage::Cpp);
+            }
+            // Everything else dumped is an FX graph
+            return Self::of_name(&dump.name).or(Some(CodeLanguage::Python));
+        }
+        // The guard tree prints each guard as a C++ guard manager call
+        if e.dynamo_cpp_guards_str.is_some() {
+            return Some(CodeLanguage::Cpp);
+        }
+        let graph = e.dynamo_output_graph.is_some()
+            || e.optimize_ddp_split_graph.is_some()
+            || e.optimize_ddp_split_child.is_some()
+            || e.compiled_autograd_graph.is_some()
+            || e.aot_forward_graph.is_some()
+            || e.aot_backward_graph.is_some()
+            || e.aot_inference_graph.is_some()
+            || e.aot_joint_graph.is_some()
+            || e.inductor_pre_grad_graph.is_some()
+            || e.inductor_post_grad_graph.is_some();
+        graph.then_some(CodeLanguage::Python)
+    }
+
+    // Language of a code artifact or graph dump, by name
+    pub(crate) fn of_name(name: &str) -> Option<Self> {
+        if name.starts_with("inductor_aot_") && name.ends_with("_code") {
+            Some(CodeLanguage::Cpp)
+        } else if name == "fx_graph_runnable"
+            || name.ends_with("_pre_grad_graph")
+            || name.ends_with("_post_grad_graph")
+            || name.starts_with("before_recompile_")
+            || name.starts_with("after_recompile_")
+        {
+            Some(CodeLanguage::Python)
+        } else {
+            None
+        }
+    }
+}
+
+// Loading syntaxes and themes takes a while, so it happens once, on first use
+struct Highlighter {
+    syntax_set: SyntaxSet,
+    theme: Theme,
+    // Terminals are mostly dark, unlike the HTML report
+    terminal_theme: Theme,
+}
+
+static HIGHLIGHTER: Lazy<Highlighter> = Lazy::new(|| {
+    let themes = ThemeSet::load_defaults();
+    Highlighter {
+        syntax_set: SyntaxSet::load_defaults_newlines(),
+        theme: themes.themes["InspiredGitHub"].clone(),
+        terminal_theme: themes.themes["base16-ocean.dark"].clone(),
+    }
+});
+
+impl Highlighter {
+    fn syntax(&self, language: CodeLanguage) -> anyhow::Result<&SyntaxReference> {
+        let extension = language.extension();
+        Ok(self
+            .syntax_set
+            .find_syntax_by_extension(extension)
+            .ok_or_else(|| ParserError::TemplateRender(format!("No syntax for .{extension}")))?)
+    }
+}
+
+/// Syntax highlight code as a `<pre>` block
+pub fn highlight_html(payload: &str, language: CodeLanguage) -> anyhow::Result<String> {
+    let highlighter = &*HIGHLIGHTER;
+    Ok(syntect::html::highlighted_html_for_string(
+        payload,
+        &highlighter.syntax_set,
+        highlighter.syntax(language)?,
+        &highlighter.theme,
+    )?)
+}
+
+/// Syntax highlight code as a page with an `L<n>` anchor per line, like `anchor_source`
+pub fn highlight_anchored_html(payload: &str, language: CodeLanguage) -> anyhow::Result<String> {
+    let highlighter = &*HIGHLIGHTER;
+    let mut lines = HighlightLines::new(highlighter.syntax(language)?, &highlighter.theme);
+    let mut html_lines = Vec::new();
+    for line in payload.lines() {
+        // The newlines syntaxes expect each line to keep its line ending
+        let line = format!("{line}\n");
+        let regions = lines.highlight_line(&line, &highlighter.syntax_set)?;
+        let html = styled_line_to_highlighted_html(&regions, IncludeBackground::No)?;
+        html_lines.push(html.replace('\n', ""));
+    }
+    Ok(anchored_lines_page(html_lines))
+}
+
+/// Syntax highlight code for a terminal, as styled regions per line
+pub fn highlight_terminal_lines(
+    payload: &str,
+    language: CodeLanguage,
+) -> anyhow::Result<Vec<Vec<(syntect::highlighting::Style, String)>>> {
+    let highlighter = &*HIGHLIGHTER;
+    let mut lines = HighlightLines::new(highlighter.syntax(language)?, &highlighter.terminal_theme);
+    let mut styled = Vec::new();
+    for line in payload.lines() {
+        let line = format!("{line}\n");
+        let regions = lines.highlight_line(&line, &highlighter.syntax_set)?;
+        styled.push(
+            regions
+                .into_iter()
+                .map(|(style, text)| (style, text.trim_end_matches('\n').to_string()))
+                .collect(),
+        );
+    }
+    Ok(styled)
 }
 
 pub struct OptimizeDdpSplitChildParser;
@@ -340,7 +837,10 @@ impl StructuredLogParser for OptimizeDdpSplitChildParser {
             let filename = format!("optimize_ddp_split_child_{}.txt", m.name);
             payload_file_output(&filename, lineno, compile_id)
         } else {
-            Err(anyhow::anyhow!("Expected OptimizeDdpSplitChild metadata"))
+            Err(
+                ParserError::SchemaMismatch("Expected OptimizeDdpSplitChild metadata".to_string())
+                    .into(),
+            )
         }
     }
 }
@@ -350,6 +850,9 @@ impl StructuredLogParser for LinkParser {
     fn name(&self) -> &'static str {
         "link_parser"
     }
+    fn envelope_keys(&self) -> Vec<String> {
+        vec!["link".to_string()]
+    }
     fn get_metadata<'e>(&self, e: &'e Envelope) -> Option<Metadata<'e>> {
         e.link.as_ref().map(|m| Metadata::Link(m))
     }
@@ -368,12 +871,12 @@ impl StructuredLogParser for LinkParser {
                 m.url.clone(),
             )]))
         } else {
-            Err(anyhow::anyhow!("Expected Link Metadata"))
+            Err(ParserError::SchemaMismatch("Expected Link Metadata".to_string()).into())
         }
     }
 }
 
-fn format_stack(stack: &StackSummary, caption: &str, open: bool) -> String {
+pub fn format_stack(stack: &StackSummary, caption: &str, open: bool) -> String {
     let mut trie = StackTrieNode::default();
     trie.insert_no_terminal(stack.to_vec());
     trie.fmt(None, caption, open).unwrap()
@@ -381,12 +884,40 @@ fn format_stack(stack: &StackSummary, caption: &str, open: bool) -> String {
 
 pub struct CompilationMetricsParser<'t> {
     pub tt: &'t TinyTemplate<'t>,
+    pub css: &'static str,
     pub stack_index: &'t RefCell<StackIndex>,
     pub symbolic_shape_specialization_index: &'t RefCell<SymbolicShapeSpecializationIndex>,
     pub guard_added_fast_index: &'t RefCell<GuardAddedFastIndex>,
     pub output_files: &'t Vec<OutputFile>,
     pub compile_id_dir: &'t PathBuf,
+    pub tensor_inputs_url: Option<String>,
+    pub metrics_pages: &'t RefCell<CompilationMetricsPageIndex>,
+}
+
+/// Render the compilation metrics page of a compile, with the runtime metrics of its graphs
+pub fn render_compilation_metrics(
+    tt: &TinyTemplate,
+    css: &'static str,
+    page: &CompilationMetricsPage,
+    runtime_metrics: &[CompilationMetricsRuntimeMetadata],
+) -> anyhow::Result<String> {
+    let context = CompilationMetricsContext {
+        css,
+        m: &page.m,
+        compile_id: &page.compile_id,
+        stack_html: &page.stack_html,
+        mini_stack_html: &page.mini_stack_html,
+        symbolic_shape_specializations: &page.symbolic_shape_specializations,
+        guards_added_fast: &page.guards_added_fast,
+        output_files: &page.output_files,
+        compile_id_dir: &page.compile_id_dir,
+        tensor_inputs_url: page.tensor_inputs_url.as_deref(),
+        runtime_metrics,
+        qps: TEMPLATE_QUERY_PARAM_SCRIPT,
+    };
+    Ok(tt.render("compilation_metrics.html", &context)?)
 }
+
 impl StructuredLogParser for CompilationMetricsParser<'_> {
     fn name(&self) -> &'static str {
         "compilation_metrics"
@@ -487,39 +1018,177 @@ impl StructuredLogParser for CompilationMetricsParser<'_> {
                 let new_str: String = parts[1..].join("");
                 new_str
             };
+            // Content addressed payloads are shared between compile ids
+            let relative_url = |x: &String| -> String {
+                if x.starts_with("payloads/") || x.starts_with("../payloads/") {
+                    format!("../{x}")
+                } else {
+                    remove_prefix(x)
+                }
+            };
             let output_files: Vec<OutputFile> = self
                 .output_files
                 .iter()
                 .map(|o| OutputFile {
-                    url: remove_prefix(&o.url),
+                    url: relative_url(&o.url),
                     name: remove_prefix(&o.name),
                     number: o.number.clone(),
                     suffix: o.suffix.clone(),
-                    readable_url: o.readable_url.as_ref().map(|u| remove_prefix(u)),
+                    readable_url: o.readable_url.as_ref().map(relative_url),
+                    group: o.group.clone(),
+                    group_start: o.group_start,
+                    hash: o.hash.clone(),
+                    lineno: o.lineno,
                 })
                 .collect();
-            let context = CompilationMetricsContext {
-                css: crate::CSS,
-                m: &m,
+            let page = CompilationMetricsPage {
+                m: m.clone(),
                 compile_id: id,
-                stack_html: stack_html,
-                mini_stack_html: mini_stack_html,
+                stack_html,
+                mini_stack_html,
                 symbolic_shape_specializations: specializations,
-                guards_added_fast: guards_added_fast,
-                output_files: &output_files,
-                compile_id_dir: &self.compile_id_dir,
-                qps: TEMPLATE_QUERY_PARAM_SCRIPT,
+                guards_added_fast,
+                output_files,
+                compile_id_dir: self.compile_id_dir.clone(),
+                tensor_inputs_url: self.tensor_inputs_url.clone(),
             };
-            let output = self.tt.render(&filename, &context)?;
+            let output = render_compilation_metrics(self.tt, self.css, &page, &[])?;
+            let mut key = compile_id.clone();
+            if let Some(c) = key.as_mut() {
+                c.attempt = None;
+            }
+            self.metrics_pages.borrow_mut().insert(key, page);
             simple_file_output(&filename, lineno, compile_id, &output)
         } else {
-            Err(anyhow::anyhow!("Expected CompilationMetrics metadata"))
+            Err(
+                ParserError::SchemaMismatch("Expected CompilationMetrics metadata".to_string())
+                    .into(),
+            )
         }
     }
 }
 
+fn format_sym_ints(values: &[SymInt], dynamic_indices: &[u64]) -> String {
+    let entries: Vec<String> = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| match v {
+            SymInt::Symbol(sym) => format!("<span class='symbolic'>{}</span>", encode_text(sym)),
+            SymInt::Int(n) if dynamic_indices.contains(&(i as u64)) => {
+                format!("<span class='symbolic'>{}</span>", n)
+            }
+            SymInt::Int(n) => n.to_string(),
+        })
+        .collect();
+    format!("[{}]", entries.join(", "))
+}
+
+/**
+ * Renders the graph inputs described by describe_tensor/describe_storage/describe_source for a
+ * compile id.  Like compilation metrics, it is run when the compilation_metrics envelope for
+ * the compile id arrives, so that the page is listed on the compilation metrics page.
+ */
+pub struct TensorInputsParser<'t> {
+    pub tt: &'t TinyTemplate<'t>,
+    pub css: &'static str,
+    pub tensor_description_index: &'t RefCell<TensorDescriptionIndex>,
+}
+impl StructuredLogParser for TensorInputsParser<'_> {
+    fn name(&self) -> &'static str {
+        "tensor_inputs"
+    }
+    fn envelope_keys(&self) -> Vec<String> {
+        vec!["compilation_metrics".to_string()]
+    }
+    fn get_metadata<'e>(&self, e: &'e Envelope) -> Option<Metadata<'e>> {
+        e.compilation_metrics
+            .as_ref()
+            .map(Metadata::CompilationMetrics)
+    }
+    fn parse<'e>(
+        &self,
+        lineno: usize,
+        _metadata: Metadata<'e>,
+        _rank: Option<u32>,
+        compile_id: &Option<CompileId>,
+        _payload: &str,
+    ) -> anyhow::Result<ParserResults> {
+        let Some(descriptions) = self
+            .tensor_description_index
+            .borrow_mut()
+            .remove(compile_id)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let source_names = |describer_id: u64, id: u64| -> Vec<String> {
+            descriptions
+                .sources
+                .iter()
+                .filter(|s| s.describer_id == describer_id && s.id == id)
+                .map(|s| s.source.clone())
+                .collect()
+        };
+        let inputs = descriptions
+            .tensors
+            .iter()
+            .map(|t| {
+                let dynamic_indices = t.dynamo_dynamic_indices.clone().unwrap_or_default();
+                let storage = t.storage.map_or("".to_string(), |storage_id| {
+                    descriptions
+                        .storages
+                        .iter()
+                        .find(|s| s.describer_id == t.describer_id && s.id == storage_id)
+                        .map_or(format!("storage {}", storage_id), |s| {
+                            format!("storage {} ({} bytes)", storage_id, s.size)
+                        })
+                });
+                let view = t.base.map_or("".to_string(), |base| {
+                    let base_sources = source_names(t.describer_id, base);
+                    if base_sources.is_empty() {
+                        format!("view of tensor {}", base)
+                    } else {
+                        format!("view of {} (tensor {})", base_sources.join(", "), base)
+                    }
+                });
+                TensorInputContext {
+                    id: t.id,
+                    sources: source_names(t.describer_id, t.id),
+                    dtype: t.dtype.clone(),
+                    device: t.device.clone(),
+                    size_html: format_sym_ints(&t.size, &dynamic_indices),
+                    stride_html: t
+                        .stride
+                        .as_ref()
+                        .map_or("".to_string(), |s| format_sym_ints(s, &[])),
+                    storage,
+                    requires_grad: t.requires_grad,
+                    is_leaf: t.is_leaf,
+                    is_parameter: t.is_parameter,
+                    view,
+                    is_dynamic: !dynamic_indices.is_empty()
+                        || t.size.iter().any(|s| matches!(s, SymInt::Symbol(_))),
+                }
+            })
+            .collect();
+
+        let filename = "tensor_inputs.html";
+        let context = TensorInputsContext {
+            css: self.css,
+            compile_id: compile_id
+                .clone()
+                .map_or("(unknown)".to_string(), |c| c.to_string()),
+            inputs,
+            qps: TEMPLATE_QUERY_PARAM_SCRIPT,
+        };
+        let output = self.tt.render(filename, &context)?;
+        simple_file_output(filename, lineno, compile_id, &output)
+    }
+}
+
 pub struct AOTAutogradBackwardCompilationMetricsParser<'t> {
     tt: &'t TinyTemplate<'t>,
+    css: &'static str,
 }
 impl StructuredLogParser for AOTAutogradBackwardCompilationMetricsParser<'_> {
     fn name(&self) -> &'static str {
@@ -544,7 +1213,7 @@ impl StructuredLogParser for AOTAutogradBackwardCompilationMetricsParser<'_> {
                 .clone()
                 .map_or("(unknown) ".to_string(), |c| format!("{cid} ", cid = c));
             let context = AOTAutogradBackwardCompilationMetricsContext {
-                css: crate::CSS,
+                css: self.css,
                 m: &m,
                 compile_id: id,
                 qps: TEMPLATE_QUERY_PARAM_SCRIPT,
@@ -552,15 +1221,17 @@ impl StructuredLogParser for AOTAutogradBackwardCompilationMetricsParser<'_> {
             let output = self.tt.render(&filename, &context)?;
             simple_file_output(&filename, lineno, compile_id, &output)
         } else {
-            Err(anyhow::anyhow!(
-                "Expected AOTAutogradBackwardCompilationMetrics metadata"
-            ))
+            Err(ParserError::SchemaMismatch(
+                "Expected AOTAutogradBackwardCompilationMetrics metadata".to_string(),
+            )
+            .into())
         }
     }
 }
 
 pub struct BwdCompilationMetricsParser<'t> {
     tt: &'t TinyTemplate<'t>,
+    css: &'static str,
 }
 impl StructuredLogParser for BwdCompilationMetricsParser<'_> {
     fn name(&self) -> &'static str {
@@ -585,7 +1256,7 @@ impl StructuredLogParser for BwdCompilationMetricsParser<'_> {
                 .clone()
                 .map_or("(unknown) ".to_string(), |c| format!("{cid} ", cid = c));
             let context = BwdCompilationMetricsContext {
-                css: crate::CSS,
+                css: self.css,
                 m: &m,
                 compile_id: id,
                 qps: TEMPLATE_QUERY_PARAM_SCRIPT,
@@ -593,7 +1264,52 @@ impl StructuredLogParser for BwdCompilationMetricsParser<'_> {
             let output = self.tt.render(&filename, &context)?;
             simple_file_output(&filename, lineno, compile_id, &output)
         } else {
-            Err(anyhow::anyhow!("Expected BwdCompilationMetrics metadata"))
+            Err(
+                ParserError::SchemaMismatch("Expected BwdCompilationMetrics metadata".to_string())
+                    .into(),
+            )
+        }
+    }
+}
+
+pub struct CompilationMetricsRuntimeParser<'t> {
+    tt: &'t TinyTemplate<'t>,
+    css: &'static str,
+}
+impl StructuredLogParser for CompilationMetricsRuntimeParser<'_> {
+    fn name(&self) -> &'static str {
+        "compilation_metrics_runtime"
+    }
+    fn get_metadata<'e>(&self, e: &'e Envelope) -> Option<Metadata<'e>> {
+        e.compilation_metrics_runtime
+            .as_ref()
+            .map(Metadata::CompilationMetricsRuntime)
+    }
+    fn parse<'e>(
+        &self,
+        lineno: usize,
+        metrics: Metadata<'e>,
+        _rank: Option<u32>,
+        compile_id: &Option<CompileId>,
+        _payload: &str,
+    ) -> anyhow::Result<ParserResults> {
+        let filename = format!("{}.html", self.name());
+        if let Metadata::CompilationMetricsRuntime(m) = metrics {
+            let context = CompilationMetricsRuntimeContext {
+                css: self.css,
+                m,
+                compile_id: compile_id
+                    .clone()
+                    .map_or("(unknown)".to_string(), |c| c.to_string()),
+                qps: TEMPLATE_QUERY_PARAM_SCRIPT,
+            };
+            let output = self.tt.render(&filename, &context)?;
+            simple_file_output(&filename, lineno, compile_id, &output)
+        } else {
+            Err(ParserError::SchemaMismatch(
+                "Expected CompilationMetricsRuntime metadata".to_string(),
+            )
+            .into())
         }
     }
 }
@@ -628,13 +1344,17 @@ impl StructuredLogParser for DumpFileParser {
                 anchor_source(payload),
             )]))
         } else {
-            Err(anyhow::anyhow!("Expected DumpFile metadata"))
+            Err(ParserError::SchemaMismatch("Expected DumpFile metadata".to_string()).into())
         }
     }
 }
 
 pub fn anchor_source(text: &str) -> String {
-    let lines: Vec<&str> = text.lines().collect();
+    anchored_lines_page(text.lines().map(|line| encode_text(line).into_owned()))
+}
+
+// Source page with one `<span id="L<n>">` per line of html, so other pages can link to lines
+fn anchored_lines_page(lines: impl IntoIterator<Item = String>) -> String {
     let mut html = String::from(
         r#"<!DOCTYPE html>
 <html lang="en">
@@ -646,10 +1366,10 @@ pub fn anchor_source(text: &str) -> String {
         pre {
             counter-reset: line;
         }
-        pre span {
+        pre > span {
             display: block;
         }
-        pre span:before {
+        pre > span:before {
             counter-increment: line;
             content: counter(line);
             display: inline-block;
@@ -657,7 +1377,7 @@ pub fn anchor_source(text: &str) -> String {
             margin-right: .5em;
             color: #888;
         }
-        pre span:target {
+        pre > span:target {
             background-color: #ffff00;
         }
     </style>
@@ -666,13 +1386,9 @@ pub fn anchor_source(text: &str) -> String {
     <pre>"#,
     );
 
-    for (i, line) in lines.iter().enumerate() {
+    for (i, line) in lines.into_iter().enumerate() {
         let line_number = i + 1;
-        html.push_str(&format!(
-            r#"<span id="L{}">{}</span>"#,
-            line_number,
-            encode_text(line)
-        ));
+        html.push_str(&format!(r#"<span id="L{}">{}</span>"#, line_number, line));
     }
 
     html.push_str(&format!(
@@ -960,12 +1676,27 @@ fn read_artifacts<T>(
     Ok(results)
 }
 
-pub struct ArtifactParser;
+pub struct ArtifactParser {
+    // Artifacts with a renderer in tlparse.toml are rendered by RenderRulesParser instead
+    render_rules: Vec<RenderRule>,
+}
+
+impl ArtifactParser {
+    pub fn new(config: &ParseConfig) -> Self {
+        ArtifactParser {
+            render_rules: config.render_rules.clone(),
+        }
+    }
+}
+
 impl StructuredLogParser for ArtifactParser {
     fn name(&self) -> &'static str {
         "artifact"
     }
     fn get_metadata<'e>(&self, e: &'e Envelope) -> Option<Metadata<'e>> {
+        if find_rule(&self.render_rules, e).is_some_and(|(rule, _)| rule.renderer.is_some()) {
+            return None;
+        }
         e.artifact.as_ref().map(|m| Metadata::Artifact(m))
     }
     fn parse<'e>(
@@ -986,17 +1717,200 @@ impl StructuredLogParser for ArtifactParser {
                     let filename: String = format!("{}.json", metadata.name);
                     payload_reformat_file_output(&filename, lineno, compile_id, format_json_pretty)
                 }
-                _ => Err(anyhow::anyhow!(
-                    "Unsupported encoding: {}",
-                    metadata.encoding
-                )),
+                _ => Err(ParserError::UnsupportedEncoding(metadata.encoding.clone()).into()),
             }
         } else {
-            Err(anyhow::anyhow!("Expected Artifact metadata"))
+            Err(ParserError::SchemaMismatch("Expected Artifact metadata".to_string()).into())
         }
     }
 }
 
+// Renders artifacts and envelope keys with the built-in renderer picked in tlparse.toml
+pub struct RenderRulesParser<'t> {
+    tt: &'t TinyTemplate<'t>,
+    css: &'static str,
+    render_rules: Vec<RenderRule>,
+    plain_text: bool,
+}
+
+impl<'t> RenderRulesParser<'t> {
+    pub fn new(tt: &'t TinyTemplate<'t>, config: &ParseConfig) -> Self {
+        RenderRulesParser {
+            tt,
+            css: config.templates.get("failures.css"),
+            render_rules: config.render_rules.clone(),
+            plain_text: config.plain_text,
+        }
+    }
+}
+
+fn json_cell(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        v => v.to_string(),
+    }
+}
+
+impl StructuredLogParser for RenderRulesParser<'_> {
+    fn name(&self) -> &'static str {
+        "render_rules"
+    }
+    fn envelope_keys(&self) -> Vec<String> {
+        self.render_rules
+            .iter()
+            .filter(|rule| rule.renderer.is_some())
+            .filter_map(|rule| match (&rule.artifact, &rule.key) {
+                (Some(artifact), _) => Some(format!("artifact ({artifact})")),
+                (_, Some(key)) => Some(key.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+    fn get_metadata<'e>(&self, e: &'e Envelope) -> Option<Metadata<'e>> {
+        find_rule(&self.render_rules, e)
+            .filter(|(rule, _)| rule.renderer.is_some())
+            .map(|(_, m)| m)
+    }
+    fn parse<'e>(
+        &self,
+        lineno: usize,
+        metadata: Metadata<'e>,
+        _rank: Option<u32>,
+        compile_id: &Option<CompileId>,
+        payload: &str,
+    ) -> anyhow::Result<ParserResults> {
+        let name = RenderRule::output_name(&metadata).ok_or_else(|| {
+            ParserError::SchemaMismatch("Expected Artifact or Other metadata".to_string())
+        })?;
+        let rule = self
+            .render_rules
+            .iter()
+            .find(|rule| rule.renderer.is_some() && rule.matches_metadata(&metadata))
+            .ok_or_else(|| ParserError::SchemaMismatch(format!("No render rule for {name}")))?;
+        // Envelope keys without a payload carry their data in the metadata
+        let content = match (&metadata, payload) {
+            (Metadata::Other(_, value), "") => serde_json::to_string_pretty(value)?,
+            _ => payload.to_string(),
+        };
+        let display_name = rule.display_name.clone().unwrap_or_else(|| name.clone());
+        match rule.renderer {
+            Some(Renderer::Json) => simple_file_output(
+                &format!("{name}.json"),
+                lineno,
+                compile_id,
+                &format_json_pretty(&content)?,
+            ),
+            Some(Renderer::PythonHighlight) | Some(Renderer::CppHighlight) => {
+                if self.plain_text {
+                    simple_file_output(&format!("{name}.txt"), lineno, compile_id, &content)
+                } else {
+                    let language = if rule.renderer == Some(Renderer::CppHighlight) {
+                        CodeLanguage::Cpp
+                    } else {
+                        CodeLanguage::Python
+                    };
+                    let html = highlight_anchored_html(&content, language)?;
+                    simple_file_output(&format!("{name}.html"), lineno, compile_id, &html)
+                }
+            }
+            Some(Renderer::TableFromJsonArray) => {
+                let rows: Vec<Value> = serde_json::from_str(&content)?;
+                let mut columns: Vec<String> = Vec::new();
+                for row in &rows {
+                    if let Value::Object(fields) = row {
+                        for key in fields.keys() {
+                            if !columns.contains(key) {
+                                columns.push(key.clone());
+                            }
+                        }
+                    }
+                }
+                let rows: Vec<Vec<String>> = rows
+                    .iter()
+                    .map(|row| match row {
+                        Value::Object(fields) => columns
+                            .iter()
+                            .map(|c| fields.get(c).map(json_cell).unwrap_or_default())
+                            .collect(),
+                        v => vec![json_cell(v)],
+                    })
+                    .collect();
+                let context = JsonTableContext {
+                    css: self.css,
+                    title: display_name,
+                    columns,
+                    rows,
+                    qps: TEMPLATE_QUERY_PARAM_SCRIPT,
+                };
+                let html = self.tt.render("json_table.html", &context)?;
+                simple_file_output(&format!("{name}.html"), lineno, compile_id, &html)
+            }
+            Some(Renderer::Markdown) => {
+                let mut body = String::new();
+                // Payloads come straight from the log, so raw HTML is shown as text and links
+                // can't run script
+                let events = pulldown_cmark::Parser::new(&content).map(|event| match event {
+                    pulldown_cmark::Event::Html(html) | pulldown_cmark::Event::InlineHtml(html) => {
+                        pulldown_cmark::Event::Text(html)
+                    }
+                    pulldown_cmark::Event::Start(pulldown_cmark::Tag::Link {
+                        link_type,
+                        dest_url,
+                        title,
+                        id,
+                    }) => pulldown_cmark::Event::Start(pulldown_cmark::Tag::Link {
+                        link_type,
+                        dest_url: safe_link_destination(dest_url),
+                        title,
+                        id,
+                    }),
+                    pulldown_cmark::Event::Start(pulldown_cmark::Tag::Image {
+                        link_type,
+                        dest_url,
+                        title,
+                        id,
+                    }) => pulldown_cmark::Event::Start(pulldown_cmark::Tag::Image {
+                        link_type,
+                        dest_url: safe_link_destination(dest_url),
+                        title,
+                        id,
+                    }),
+                    event => event,
+                });
+                pulldown_cmark::html::push_html(&mut body, events);
+                let context = MarkdownContext {
+                    css: self.css,
+                    title: display_name,
+                    body,
+                    qps: TEMPLATE_QUERY_PARAM_SCRIPT,
+                };
+                let html = self.tt.render("markdown.html", &context)?;
+                simple_file_output(&format!("{name}.html"), lineno, compile_id, &html)
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+// Keeps http(s), relative and in-page link destinations; anything else (javascript:, data:,
+// ...) becomes an in-page link to nowhere
+fn safe_link_destination(dest_url: pulldown_cmark::CowStr) -> pulldown_cmark::CowStr {
+    let scheme_end = dest_url.find([':', '/', '?', '#']);
+    let safe = match scheme_end {
+        Some(i) if dest_url[i..].starts_with(':') => {
+            let scheme = dest_url[..i].to_ascii_lowercase();
+            scheme == "http" || scheme == "https"
+        }
+        _ => true,
+    };
+    if safe {
+        dest_url
+    } else {
+        "#".into()
+    }
+}
+
 fn render_sym_expr_trie(
     expr: u64,
     sym_expr_info_index: &SymExprInfoIndex,
@@ -1066,12 +1980,19 @@ fn render_sym_expr_trie(
 
 pub struct PropagateRealTensorsParser<'t> {
     pub tt: &'t TinyTemplate<'t>,
+    pub css: &'static str,
     pub sym_expr_info_index: &'t SymExprInfoIndex,
 }
 impl StructuredLogParser for PropagateRealTensorsParser<'_> {
     fn name(&self) -> &'static str {
         "guard_added"
     }
+    fn envelope_keys(&self) -> Vec<String> {
+        vec![
+            "guard_added".to_string(),
+            "propagate_real_tensors_provenance".to_string(),
+        ]
+    }
     fn get_metadata<'e>(&self, e: &'e Envelope) -> Option<Metadata<'e>> {
         if let Some(m) = e.propagate_real_tensors_provenance.as_ref() {
             return Some(Metadata::SymbolicShapePropagateRealTensor(m));
@@ -1107,17 +2028,14 @@ impl StructuredLogParser for PropagateRealTensorsParser<'_> {
             );
 
             let mut visited = HashSet::new();
-            let sym_expr_trie_html = render_sym_expr_trie(
-                m.expr_node_id.unwrap(),
-                self.sym_expr_info_index,
-                0,
-                &mut visited,
-            )
-            .unwrap_or("".to_string());
+            let sym_expr_trie_html = m
+                .expr_node_id
+                .and_then(|id| render_sym_expr_trie(id, self.sym_expr_info_index, 0, &mut visited))
+                .unwrap_or("".to_string());
 
             let context = SymbolicGuardContext {
-                css: crate::CSS,
-                expr: m.expr.clone().unwrap(),
+                css: self.css,
+                expr: m.expr.clone().unwrap_or_default(),
                 user_stack_html: user_stack_html,
                 framework_stack_html: framework_stack_html,
                 sym_expr_trie_html: sym_expr_trie_html,
@@ -1126,9 +2044,10 @@ impl StructuredLogParser for PropagateRealTensorsParser<'_> {
             let output = self.tt.render(&filename, &context)?;
             simple_file_output(&filename, lineno, compile_id, &output)
         } else {
-            Err(anyhow::anyhow!(
-                "Expected SymbolicShapePropagateRealTensor metadata"
-            ))
+            Err(ParserError::SchemaMismatch(
+                "Expected SymbolicShapePropagateRealTensor metadata".to_string(),
+            )
+            .into())
         }
     }
 }
@@ -1178,10 +2097,22 @@ pub fn default_parsers<'t>(
         Box::new(DynamoGuardParser { tt }),
         Box::new(InductorOutputCodeParser::new(parser_config)),
         Box::new(OptimizeDdpSplitChildParser),
-        Box::new(AOTAutogradBackwardCompilationMetricsParser { tt }), // TODO: use own tt instances
-        Box::new(BwdCompilationMetricsParser { tt }),                 // TODO: use own tt instances
+        // TODO: use own tt instances
+        Box::new(AOTAutogradBackwardCompilationMetricsParser {
+            tt,
+            css: parser_config.templates.get("main.css"),
+        }),
+        Box::new(BwdCompilationMetricsParser {
+            tt,
+            css: parser_config.templates.get("main.css"),
+        }),
+        Box::new(CompilationMetricsRuntimeParser {
+            tt,
+            css: parser_config.templates.get("main.css"),
+        }),
         Box::new(LinkParser),
-        Box::new(ArtifactParser),
+        Box::new(RenderRulesParser::new(tt, parser_config)),
+        Box::new(ArtifactParser::new(parser_config)),
         Box::new(DumpFileParser),
     ];
 
@@ -0,0 +1,282 @@
+//! Parser plugins loaded from shared libraries at runtime (`--plugin-dir`).
+//!
+//! This lets the stock tlparse binary run analyses that live out of tree, without building a
+//! binary that links tlparse and fills `ParseConfig.custom_parsers`.
+//!
+//! A plugin is a shared library (`.so`, `.dylib`) exporting
+//!
+//! ```c
+//! const struct tlparse_plugin_v1 *tlparse_plugin_v1(void);
+//! ```
+//!
+//! which returns a pointer to a static [`TlparsePluginV1`]. Everything crossing the boundary is
+//! a NUL-terminated UTF-8 JSON string, so the ABI doesn't depend on tlparse's Rust types:
+//!
+//! * `parse` is called for every envelope that has `metadata_key` as an unrecognized key (see
+//!   `Envelope::other`), with
+//!   `{"key": .., "metadata": .., "lineno": .., "rank": .., "compile_id": .., "payload": ..}`.
+//! * `finish`, if not NULL, is called once after the last log line.
+//! * Both return `{"outputs": [{"file": name, "content": text} | {"link": name, "url": url}]}`
+//!   or `{"error": message, "error_kind": kind}`, allocated by the plugin and handed back to
+//!   its `free`. `error_kind` is optional and shows up in the parser error stats.
+//!
+//! Files returned from `parse` go in the compile id directory, like any other parser output;
+//! files returned from `finish` go at the top of the output directory and are linked from
+//! index.html.
+
+use crate::parsers::{
+    build_file_path, CompileId, Envelope, FinishContext, Metadata, ParserError, ParserOutput,
+    ParserResults, StructuredLogParser,
+};
+use anyhow::{anyhow, bail};
+use serde::Deserialize;
+use std::ffi::{c_char, CStr, CString};
+use std::path::{Path, PathBuf};
+
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+pub const PLUGIN_ENTRY_POINT: &str = "tlparse_plugin_v1";
+
+#[repr(C)]
+pub struct TlparsePluginV1 {
+    // Must be PLUGIN_ABI_VERSION
+    pub abi_version: u32,
+    // Name of the plugin, for error logging
+    pub name: *const c_char,
+    // Envelope key this plugin parses
+    pub metadata_key: *const c_char,
+    pub parse: extern "C" fn(input: *const c_char) -> *mut c_char,
+    pub finish: Option<extern "C" fn() -> *mut c_char>,
+    // Releases strings returned by parse and finish
+    pub free: extern "C" fn(output: *mut c_char),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PluginOutput {
+    File { file: String, content: String },
+    Link { link: String, url: String },
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginResponse {
+    #[serde(default)]
+    outputs: Vec<PluginOutput>,
+    error: Option<String>,
+    // Category of the error, for the per-kind parser error stats
+    error_kind: Option<String>,
+}
+
+pub struct PluginParser {
+    handle: *mut libc::c_void,
+    plugin: *const TlparsePluginV1,
+    // Plugins live until the process exits, so their names are leaked to satisfy
+    // StructuredLogParser::name
+    name: &'static str,
+    metadata_key: String,
+}
+
+impl PluginParser {
+    fn plugin(&self) -> &TlparsePluginV1 {
+        // SAFETY: checked non-null at load time, and the library stays loaded until drop
+        unsafe { &*self.plugin }
+    }
+
+    fn call(&self, f: impl FnOnce() -> *mut c_char) -> anyhow::Result<Vec<PluginOutput>> {
+        let raw = f();
+        if raw.is_null() {
+            bail!("plugin {} returned NULL", self.name);
+        }
+        // SAFETY: the plugin contract is to return a NUL-terminated string, which we copy
+        // before handing it back to the plugin to free
+        let response = unsafe { CStr::from_ptr(raw) }
+            .to_string_lossy()
+            .into_owned();
+        (self.plugin().free)(raw);
+        let response: PluginResponse = serde_json::from_str(&response)?;
+        if let Some(message) = response.error {
+            return Err(ParserError::Custom {
+                kind: response.error_kind.unwrap_or_else(|| "plugin".to_string()),
+                message,
+            }
+            .into());
+        }
+        Ok(response.outputs)
+    }
+}
+
+impl StructuredLogParser for PluginParser {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn envelope_keys(&self) -> Vec<String> {
+        vec![self.metadata_key.clone()]
+    }
+
+    fn get_metadata<'e>(&self, e: &'e Envelope) -> Option<Metadata<'e>> {
+        e.other(&self.metadata_key)
+    }
+
+    fn parse<'e>(
+        &self,
+        lineno: usize,
+        metadata: Metadata<'e>,
+        rank: Option<u32>,
+        compile_id: &Option<CompileId>,
+        payload: &str,
+    ) -> anyhow::Result<ParserResults> {
+        let Metadata::Other(key, metadata) = metadata else {
+            bail!("Expected Other metadata");
+        };
+        let input = CString::new(
+            serde_json::json!({
+                "key": key,
+                "metadata": metadata,
+                "lineno": lineno,
+                "rank": rank,
+                "compile_id": compile_id,
+                "payload": payload,
+            })
+            .to_string(),
+        )?;
+        let outputs = self.call(|| (self.plugin().parse)(input.as_ptr()))?;
+        Ok(outputs
+            .into_iter()
+            .map(|output| match output {
+                PluginOutput::File { file, content } => {
+                    ParserOutput::File(build_file_path(&file, lineno, compile_id), content)
+                }
+                PluginOutput::Link { link, url } => ParserOutput::Link(link, url),
+            })
+            .collect())
+    }
+
+    fn finish(&self, _ctx: &FinishContext) -> anyhow::Result<ParserResults> {
+        let Some(finish) = self.plugin().finish else {
+            return Ok(Vec::new());
+        };
+        let outputs = self.call(|| finish())?;
+        Ok(outputs
+            .into_iter()
+            .map(|output| match output {
+                PluginOutput::File { file, content } => {
+                    ParserOutput::GlobalFile(PathBuf::from(file), content)
+                }
+                PluginOutput::Link { link, url } => ParserOutput::Link(link, url),
+            })
+            .collect())
+    }
+}
+
+#[cfg(unix)]
+impl Drop for PluginParser {
+    fn drop(&mut self) {
+        // SAFETY: handle came from dlopen and nothing from the library is used after this
+        unsafe {
+            libc::dlclose(self.handle);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn dlerror() -> String {
+    // SAFETY: dlerror returns NULL or a NUL-terminated string owned by libc
+    unsafe {
+        let err = libc::dlerror();
+        if err.is_null() {
+            "unknown error".to_string()
+        } else {
+            CStr::from_ptr(err).to_string_lossy().into_owned()
+        }
+    }
+}
+
+fn plugin_str(s: *const c_char, what: &str, path: &Path) -> anyhow::Result<String> {
+    if s.is_null() {
+        bail!("Plugin {} has a NULL {what}", path.display());
+    }
+    // SAFETY: non-null, and the plugin contract is a static NUL-terminated string
+    Ok(unsafe { CStr::from_ptr(s) }.to_str()?.to_string())
+}
+
+#[cfg(unix)]
+pub fn load_plugin(path: &Path) -> anyhow::Result<PluginParser> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes())?;
+    let entry_point = CString::new(PLUGIN_ENTRY_POINT)?;
+    // SAFETY: loading a library runs its initializers; plugins are trusted like any other
+    // code the user asks us to run
+    let handle = unsafe { libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+    if handle.is_null() {
+        bail!("Failed to load plugin {}: {}", path.display(), dlerror());
+    }
+    let close = |err: anyhow::Error| {
+        // SAFETY: handle came from dlopen above and nothing from it escapes on error
+        unsafe {
+            libc::dlclose(handle);
+        }
+        err
+    };
+    // SAFETY: handle is a live library handle
+    let symbol = unsafe { libc::dlsym(handle, entry_point.as_ptr()) };
+    if symbol.is_null() {
+        return Err(close(anyhow!(
+            "Plugin {} does not export {PLUGIN_ENTRY_POINT}: {}",
+            path.display(),
+            dlerror()
+        )));
+    }
+    // SAFETY: the plugin contract is that the entry point has this signature
+    let entry: extern "C" fn() -> *const TlparsePluginV1 = unsafe { std::mem::transmute(symbol) };
+    let plugin = entry();
+    if plugin.is_null() {
+        return Err(close(anyhow!(
+            "Plugin {} returned NULL from {PLUGIN_ENTRY_POINT}",
+            path.display()
+        )));
+    }
+    // SAFETY: non-null, and the plugin contract is a pointer to a static descriptor
+    let descriptor = unsafe { &*plugin };
+    if descriptor.abi_version != PLUGIN_ABI_VERSION {
+        return Err(close(anyhow!(
+            "Plugin {} has ABI version {}, expected {PLUGIN_ABI_VERSION}",
+            path.display(),
+            descriptor.abi_version
+        )));
+    }
+    let name = plugin_str(descriptor.name, "name", path).map_err(close)?;
+    let metadata_key = plugin_str(descriptor.metadata_key, "metadata_key", path).map_err(close)?;
+    Ok(PluginParser {
+        handle,
+        plugin,
+        name: Box::leak(name.into_boxed_str()),
+        metadata_key,
+    })
+}
+
+#[cfg(not(unix))]
+pub fn load_plugin(path: &Path) -> anyhow::Result<PluginParser> {
+    bail!(
+        "Failed to load plugin {}: plugins are only supported on unix",
+        path.display()
+    )
+}
+
+/// Load every shared library in `dir` as a parser plugin, in file name order.
+pub fn load_plugins(dir: &Path) -> anyhow::Result<Vec<Box<dyn StructuredLogParser>>> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|e| anyhow!("Couldn't access plugin directory {}: {e}", dir.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == "so" || ext == "dylib")
+        })
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| Ok(Box::new(load_plugin(path)?) as Box<dyn StructuredLogParser>))
+        .collect()
+}
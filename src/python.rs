@@ -0,0 +1,197 @@
+//! Python bindings (the `tlparse._tlparse` extension module built by maturin), re-exported by
+//! `python/tlparse/__init__.py`.
+//!
+//! Results are returned as plain dicts and lists of dicts, so they can go straight into
+//! `pandas.DataFrame`.
+
+use fxhash::FxHashMap;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::config::{Templates, TlparseConfig};
+use crate::execution_order::ExecOrderIssue;
+use crate::{GraphRuntime, ParseConfig};
+
+fn runtime_error(err: anyhow::Error) -> PyErr {
+    PyRuntimeError::new_err(format!("{err:#}"))
+}
+
+// serde values cross the boundary through the json module, like the outputs tlparse writes
+fn to_py<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+fn from_py<T: DeserializeOwned>(obj: &Bound<'_, PyAny>) -> PyResult<T> {
+    let json: String = obj
+        .py()
+        .import("json")?
+        .call_method1("dumps", (obj,))?
+        .extract()?;
+    serde_json::from_str(&json).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Parse a TORCH_TRACE log.
+///
+/// Returns a dict with `compile_ids`, `artifacts`, `metrics` and `failures` (lists of flat
+/// records, see `ParseSummary`) and `files`, every output file by relative path.
+#[pyfunction]
+#[pyo3(signature = (
+    path,
+    *,
+    strict = false,
+    strict_compile_id = false,
+    plain_text = false,
+    export = false,
+    inductor_provenance = false,
+    only_parsers = Vec::new(),
+    skip_parsers = Vec::new(),
+    content_addressed = false,
+    plugin_dir = None,
+    config = None,
+    template_dir = None,
+))]
+#[allow(clippy::too_many_arguments)]
+fn parse(
+    py: Python<'_>,
+    path: PathBuf,
+    strict: bool,
+    strict_compile_id: bool,
+    plain_text: bool,
+    export: bool,
+    inductor_provenance: bool,
+    only_parsers: Vec<String>,
+    skip_parsers: Vec<String>,
+    content_addressed: bool,
+    plugin_dir: Option<PathBuf>,
+    config: Option<PathBuf>,
+    template_dir: Option<PathBuf>,
+) -> PyResult<PyObject> {
+    // Release the GIL while parsing; parsers aren't Send, so the config is built in here too
+    let (output, summary) = py
+        .allow_threads(|| -> anyhow::Result<_> {
+            let custom_parsers = match plugin_dir {
+                Some(ref dir) => crate::plugins::load_plugins(dir)?,
+                None => Vec::new(),
+            };
+            let tlparse_config = match config {
+                Some(ref path) => TlparseConfig::load(path)?,
+                None => TlparseConfig::default(),
+            };
+            let templates = match template_dir {
+                Some(ref dir) => Templates::load(dir)?,
+                None => Templates::default(),
+            };
+            let config = ParseConfig {
+                strict,
+                strict_compile_id,
+                custom_parsers,
+                plain_text,
+                export,
+                inductor_provenance,
+                render_rules: tlparse_config.rules,
+                only_parsers,
+                skip_parsers,
+                content_addressed,
+                templates,
+                ..Default::default()
+            };
+            crate::parse_path_summary(&path, &config)
+        })
+        .map_err(runtime_error)?;
+
+    let result = to_py(py, &summary)?;
+    let files = PyDict::new(py);
+    for (path, content) in output {
+        files.set_item(path.to_string_lossy(), content)?;
+    }
+    result.bind(py).set_item("files", files)?;
+    Ok(result)
+}
+
+/// Compare per-rank runtime estimations graph by graph.
+///
+/// `runtimes` is a list of `{"rank": int, "graph": str, "ops": [{"name": str,
+/// "estimated_runtime_ns": float}]}`. Returns `RuntimeAnalysis` as a dict, or None.
+#[pyfunction]
+fn analyze_graph_runtime_deltas(
+    py: Python<'_>,
+    runtimes: &Bound<'_, PyAny>,
+) -> PyResult<Option<PyObject>> {
+    let runtimes: Vec<GraphRuntime> = from_py(runtimes)?;
+    crate::analyze_graph_runtime_deltas(&runtimes)
+        .map(|analysis| to_py(py, &analysis))
+        .transpose()
+}
+
+/// Align per-rank execution orders by index and flag schedule and cache mismatches.
+///
+/// `exec_orders` maps rank to a list of compile ids; `collective_schedules` and
+/// `cache_status` are keyed by `(rank, compile_id)`. Returns one dict per index with
+/// `index`, `by_rank` (rank to compile id) and `issues`.
+#[pyfunction]
+#[pyo3(signature = (
+    exec_orders,
+    collective_schedules = FxHashMap::default(),
+    cache_status = FxHashMap::default(),
+))]
+fn analyze_execution_order<'py>(
+    py: Python<'py>,
+    exec_orders: FxHashMap<u32, Vec<String>>,
+    collective_schedules: FxHashMap<(u32, String), Vec<String>>,
+    cache_status: FxHashMap<(u32, String), String>,
+) -> PyResult<Bound<'py, PyList>> {
+    let report = crate::analyze_execution_order(&exec_orders, &collective_schedules, &cache_status);
+    let rows = PyList::empty(py);
+    for row in report.by_index {
+        let issues: Vec<&str> = row
+            .issues
+            .iter()
+            .map(|issue| match issue {
+                ExecOrderIssue::ScheduleMismatch => "schedule_mismatch",
+                ExecOrderIssue::CacheMismatch => "cache_mismatch",
+            })
+            .collect();
+        let dict = PyDict::new(py);
+        dict.set_item("index", row.index)?;
+        dict.set_item("by_rank", row.by_rank)?;
+        dict.set_item("issues", issues)?;
+        rows.append(dict)?;
+    }
+    Ok(rows)
+}
+
+/// Compile ids from a `graph_execution_order` artifact payload.
+#[pyfunction]
+fn parse_graph_execution_order(payload: &str) -> PyResult<Vec<String>> {
+    crate::parse_graph_execution_order(payload).map_err(runtime_error)
+}
+
+/// Run the tlparse command line with `argv` (including the program name), returning the
+/// exit code.
+#[pyfunction]
+fn main(py: Python<'_>, argv: Vec<String>) -> i32 {
+    match py.allow_threads(|| crate::cli::run(argv)) {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("Error: {err:?}");
+            1
+        }
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "_tlparse")]
+fn tlparse_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze_graph_runtime_deltas, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze_execution_order, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_graph_execution_order, m)?)?;
+    m.add_function(wrap_pyfunction!(main, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,61 @@
+// Search box over search_index.js (see src/search.rs).  Every query word must match part of a
+// token in the document; results link to the first line the first word matches on.
+document.addEventListener('DOMContentLoaded', function () {
+    const index = window.tlparseSearchIndex;
+    const input = document.getElementById('search-input');
+    const kindSelect = document.getElementById('search-kind');
+    const results = document.getElementById('search-results');
+    if (!index || !input) return;
+    const MAX_RESULTS = 200;
+    const tokens = Object.keys(index.tokens).map(token => [token.toLowerCase(), index.tokens[token]]);
+
+    const kinds = [...new Set(index.docs.map(doc => doc.kind))].sort();
+    for (const kind of kinds) {
+        const option = document.createElement('option');
+        option.value = option.textContent = kind;
+        kindSelect.appendChild(option);
+    }
+
+    // Document -> first matching line, for one query word
+    function matchWord(word) {
+        const hits = new Map();
+        for (const [token, postings] of tokens) {
+            if (!token.includes(word)) continue;
+            for (let i = 0; i < postings.length; i += 2) {
+                const doc = postings[i], line = postings[i + 1];
+                if (!hits.has(doc) || hits.get(doc) > line) hits.set(doc, line);
+            }
+        }
+        return hits;
+    }
+
+    function search() {
+        results.replaceChildren();
+        const words = input.value.toLowerCase().split(/[^a-z0-9_.]+/).filter(word => word.length > 0);
+        if (words.length === 0) return;
+        let hits = null;
+        for (const word of words) {
+            const wordHits = matchWord(word);
+            hits = hits === null ? wordHits : new Map([...hits].filter(([doc]) => wordHits.has(doc)));
+        }
+        const matches = [...hits].filter(([doc]) => !kindSelect.value || index.docs[doc].kind === kindSelect.value);
+        const summary = document.createElement('p');
+        summary.textContent = matches.length + ' match(es)' + (matches.length > MAX_RESULTS ? ', showing the first ' + MAX_RESULTS : '');
+        results.appendChild(summary);
+        const list = document.createElement('ul');
+        for (const [doc, line] of matches.slice(0, MAX_RESULTS)) {
+            const { compile_id, kind, url, anchored } = index.docs[doc];
+            const item = document.createElement('li');
+            const link = document.createElement('a');
+            link.href = anchored ? url + '#L' + line : url;
+            link.textContent = compile_id + ' ' + kind;
+            item.appendChild(link);
+            item.appendChild(document.createTextNode(' line ' + line));
+            list.appendChild(item);
+        }
+        results.appendChild(list);
+    }
+
+    input.addEventListener('input', search);
+    kindSelect.addEventListener('change', search);
+});
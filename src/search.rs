@@ -0,0 +1,82 @@
+//! Full text search over the report: payloads (graphs, guards, output code, ...) and failure
+//! reasons are broken into identifier-like tokens, and every token maps to the documents it
+//! appears in with the first line it appears on.  The index is written as `search_index.js`, a
+//! script rather than JSON so the index page can load it from disk without a server.
+//!
+//! The search box on the index page matches query words as substrings of tokens, so `aten.foo`
+//! finds `torch.ops.aten.foo.default`.
+
+use fxhash::FxHashSet;
+use serde::Serialize;
+
+use crate::types::FxIndexMap;
+
+// Longer tokens are mostly hashes and base64 blobs nobody searches for
+const MAX_TOKEN_LEN: usize = 64;
+
+#[derive(Debug, Serialize)]
+struct SearchDoc {
+    compile_id: String,
+    // Artifact or log type, e.g. dynamo_output_graph
+    kind: String,
+    url: String,
+    // Whether url has L<n> line anchors
+    anchored: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct SearchIndex {
+    docs: Vec<SearchDoc>,
+    // Token to a flat list of (doc, first line) pairs
+    tokens: FxIndexMap<String, Vec<u32>>,
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+impl SearchIndex {
+    pub fn add(
+        &mut self,
+        compile_id: String,
+        kind: String,
+        url: String,
+        anchored: bool,
+        text: &str,
+    ) {
+        let doc = self.docs.len() as u32;
+        self.docs.push(SearchDoc {
+            compile_id,
+            kind,
+            url,
+            anchored,
+        });
+        let mut seen: FxHashSet<&str> = FxHashSet::default();
+        for (i, line) in text.lines().enumerate() {
+            for token in line.split(|c| !is_token_char(c)) {
+                let token = token.trim_matches('.');
+                if token.len() < 3
+                    || token.len() > MAX_TOKEN_LEN
+                    || token.chars().all(|c| c.is_ascii_digit() || c == '.')
+                    || !seen.insert(token)
+                {
+                    continue;
+                }
+                let posting = [doc, i as u32 + 1];
+                match self.tokens.get_mut(token) {
+                    Some(postings) => postings.extend(posting),
+                    None => {
+                        self.tokens.insert(token.to_string(), posting.to_vec());
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn to_js(&self) -> anyhow::Result<String> {
+        Ok(format!(
+            "window.tlparseSearchIndex = {};\n",
+            serde_json::to_string(self)?
+        ))
+    }
+}
@@ -0,0 +1,426 @@
+//! `tlparse serve`: the report of a log over HTTP on localhost, at the same URLs as the static
+//! output, so links between pages work unchanged.
+//!
+//! Opening a log only indexes where each compile id's envelopes are, in one pass that doesn't
+//! run any parser.  The first request for one of a compile id's files parses the log up to the
+//! compile id's last envelope: outputs are numbered in log order, so its files, and those of every
+//! compile id that ends before it, get the names the full report gives them.  Pages about the
+//! whole run (the index, the stack trie, failures, the reports) need every envelope, so requesting
+//! one renders the full report, which serves every page from then on.
+//!
+//! Either way payload dumps, their highlighted views and raw.log are only produced when they're
+//! requested, reading the payload back from the log at the offset of its envelope.  Everything
+//! rendered is cached.
+
+use anyhow::Context;
+use fxhash::{FxHashMap, FxHashSet};
+use serde::Deserialize;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use tinytemplate::TinyTemplate;
+
+use crate::parsers::CodeLanguage;
+use crate::templates::TEMPLATE_SERVE_COMPILE_DIR;
+use crate::types::{CompileId, FxIndexMap, LogSpan, OutputFile, ServeCompileDirContext};
+use crate::{parse_log, DeferredOutput, DeferredOutputs, ParseConfig, ParseSummary};
+
+pub struct LazyReport<'c> {
+    log: PathBuf,
+    config: &'c ParseConfig,
+    // Every envelope of the log, in order
+    spans: Vec<LogSpan>,
+    // Envelopes by the directory their compile id's outputs go in, as indexes into spans, with
+    // the compile id
+    compile_dirs: FxIndexMap<String, (String, Vec<usize>)>,
+    // Compile directories whose files have been rendered
+    parsed: FxHashSet<String>,
+    // Records of the full report, once it's rendered
+    summary: Option<ParseSummary>,
+    // Outputs rendered so far, including deferred ones that have been requested
+    outputs: FxHashMap<PathBuf, String>,
+    deferred: DeferredOutputs,
+    // Language of every code output that has a highlighted view
+    languages: FxHashMap<PathBuf, CodeLanguage>,
+    // Files of each parsed compile directory, in the order its page lists them
+    listings: FxHashMap<String, Vec<OutputFile>>,
+}
+
+// The parts of an envelope the index needs; the compile id is read like Envelope's
+#[derive(Deserialize)]
+struct EnvelopeIds {
+    #[serde(flatten)]
+    compile_id: Option<CompileId>,
+    str: Option<serde::de::IgnoredAny>,
+}
+
+impl<'c> LazyReport<'c> {
+    pub fn open(log: &Path, config: &'c ParseConfig) -> anyhow::Result<Self> {
+        if !log.is_file() {
+            anyhow::bail!("{} is not a file", log.display())
+        }
+        let mut report = LazyReport {
+            log: log.to_path_buf(),
+            config,
+            spans: Vec::new(),
+            compile_dirs: FxIndexMap::default(),
+            parsed: FxHashSet::default(),
+            summary: None,
+            outputs: FxHashMap::default(),
+            deferred: DeferredOutputs::default(),
+            languages: FxHashMap::default(),
+            listings: FxHashMap::default(),
+        };
+        report.index_envelopes()?;
+        Ok(report)
+    }
+
+    // One pass over the log recording where each envelope and its payload are, by the directory
+    // its outputs go in
+    fn index_envelopes(&mut self) -> anyhow::Result<()> {
+        let mut reader = BufReader::new(File::open(&self.log)?);
+        let mut offset = 0;
+        let mut line = Vec::new();
+        for lineno in 1.. {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 {
+                break;
+            }
+            let range = offset..offset + read as u64;
+            offset = range.end;
+            // Payload lines belong to the envelope above, and so do the lines a parse skips (blank
+            // or not UTF-8)
+            let text = std::str::from_utf8(&line).map(|l| l.trim_end_matches(['\n', '\r']));
+            if line.starts_with(b"\t") || text.map_or(true, str::is_empty) {
+                if let Some(span) = self.spans.last_mut() {
+                    span.range.end = range.end;
+                }
+                continue;
+            }
+            let i = self.spans.len();
+            self.spans.push(LogSpan { lineno, range });
+            let Some(envelope) = text
+                .ok()
+                .and_then(envelope_json)
+                .and_then(|json| serde_json::from_str::<EnvelopeIds>(json).ok())
+            else {
+                continue;
+            };
+            // Interned strings don't produce files
+            if envelope.str.is_some() {
+                continue;
+            }
+            let (dir, compile_id) = match envelope.compile_id {
+                Some(compile_id) => (compile_id.as_directory_name(), compile_id.to_string()),
+                None => (format!("unknown_{lineno}"), "(unknown)".to_string()),
+            };
+            self.compile_dirs
+                .entry(dir)
+                .or_insert_with(|| (compile_id, Vec::new()))
+                .1
+                .push(i);
+        }
+        Ok(())
+    }
+
+    /// Language of the code output at `path`, if the report highlights it
+    pub fn language(&self, path: &Path) -> Option<CodeLanguage> {
+        self.languages.get(path).copied()
+    }
+
+    /// Relative paths of every output rendered so far, or that can be rendered from what has
+    /// been parsed so far
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.outputs.keys().chain(self.deferred.keys())
+    }
+
+    /// Contents of the output at `path`, parsing what it comes from first if needed
+    pub fn get(&mut self, path: &Path) -> anyhow::Result<Option<&str>> {
+        if !self.outputs.contains_key(path) && !self.deferred.contains_key(path) {
+            match compile_dir_of(path) {
+                Some(dir) if self.compile_dirs.contains_key(&dir) => {
+                    if !self.parsed.contains(&dir) {
+                        self.parse_through(&dir)?;
+                    }
+                    if path == Path::new(&dir).join("index.html") {
+                        self.render_listing(&dir)?;
+                    }
+                }
+                _ if self.summary.is_none() => self.render_full_report()?,
+                _ => {}
+            }
+        }
+        if !self.outputs.contains_key(path) {
+            let Some(deferred) = self.deferred.get(path).cloned() else {
+                return Ok(None);
+            };
+            let content = match deferred {
+                DeferredOutput::Payload { lineno } => self.envelope(lineno)?.1,
+                DeferredOutput::Highlighted { source, language } => {
+                    let source_content = self
+                        .get(&source)?
+                        .with_context(|| format!("Missing output {}", source.display()))?;
+                    crate::parsers::highlight_anchored_html(source_content, language)?
+                }
+                DeferredOutput::RawLog => std::fs::read_to_string(&self.log)?,
+            };
+            self.deferred.remove(path);
+            self.outputs.insert(path.to_path_buf(), content);
+        }
+        Ok(self.outputs.get(path).map(String::as_str))
+    }
+
+    // Parse the log up to the last envelope of a compile directory, keeping the files of every
+    // compile directory that ends by then.  The parse numbers them as the full report does, since
+    // the envelopes after it can't change the outputs before them.
+    fn parse_through(&mut self, dir: &str) -> anyhow::Result<()> {
+        let end = self.compile_dirs[dir].1.last().copied().unwrap_or_default();
+        if end + 1 == self.spans.len() {
+            return self.render_full_report();
+        }
+        let mut deferred = DeferredOutputs::default();
+        let (output, summary) = parse_log(
+            &self.log,
+            self.config,
+            Some(&mut deferred),
+            Some(&self.spans[..=end]),
+        )?;
+
+        let complete: FxHashSet<String> = self
+            .compile_dirs
+            .iter()
+            .filter(|(dir, (_, indexes))| {
+                !self.parsed.contains(*dir) && indexes.last().is_some_and(|&last| last <= end)
+            })
+            .map(|(dir, _)| dir.clone())
+            .collect();
+        let keep = |path: &Path| {
+            path.starts_with("payloads")
+                || compile_dir_of(path).is_some_and(|dir| complete.contains(&dir))
+        };
+        self.outputs
+            .extend(output.into_iter().filter(|(path, _)| keep(path)));
+        self.add_deferred(deferred.into_iter().filter(|(path, _)| keep(path)));
+        self.add_listings(&summary, &complete);
+        self.parsed.extend(complete);
+        Ok(())
+    }
+
+    /// Parse the whole log, replacing everything rendered so far with the full report
+    pub fn render_full_report(&mut self) -> anyhow::Result<()> {
+        let mut deferred = DeferredOutputs::default();
+        let (output, summary) = parse_log(&self.log, self.config, Some(&mut deferred), None)?;
+        self.outputs = output.into_iter().collect();
+        self.deferred.clear();
+        self.languages.clear();
+        self.add_deferred(deferred.into_iter());
+        self.listings.clear();
+        self.parsed = self.compile_dirs.keys().cloned().collect();
+        self.add_listings(&summary, &self.parsed.clone());
+        self.summary = Some(summary);
+        Ok(())
+    }
+
+    // Files of the given compile directories, in the order the index lists them
+    fn add_listings(&mut self, summary: &ParseSummary, dirs: &FxHashSet<String>) {
+        for file in summary.files.values().flatten() {
+            if let Some(dir) = compile_dir_of(Path::new(&file.url)).filter(|d| dirs.contains(d)) {
+                self.listings.entry(dir).or_default().push(file.clone());
+            }
+        }
+    }
+
+    /// Records of the whole log, rendering the full report first if needed
+    pub fn summary(&mut self) -> anyhow::Result<&ParseSummary> {
+        if self.summary.is_none() {
+            self.render_full_report()?;
+        }
+        Ok(self.summary.as_ref().unwrap())
+    }
+
+    fn add_deferred(&mut self, deferred: impl Iterator<Item = (PathBuf, DeferredOutput)>) {
+        for (path, output) in deferred {
+            if let DeferredOutput::Highlighted {
+                ref source,
+                language,
+            } = output
+            {
+                self.languages.insert(source.clone(), language);
+            }
+            self.deferred.insert(path, output);
+        }
+    }
+
+    // The page of a compile directory, listing its files
+    fn render_listing(&mut self, dir: &str) -> anyhow::Result<()> {
+        let absolute = |url: &str| {
+            if url.contains("://") {
+                url.to_string()
+            } else {
+                format!("/{url}")
+            }
+        };
+        let files = self
+            .listings
+            .get(dir)
+            .map(|files| {
+                files
+                    .iter()
+                    .map(|file| OutputFile {
+                        url: absolute(&file.url),
+                        readable_url: file.readable_url.as_deref().map(absolute),
+                        ..file.clone()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let context = ServeCompileDirContext {
+            css: self.config.templates.get("main.css"),
+            compile_id: self.compile_dirs[dir].0.clone(),
+            files,
+        };
+        let mut tt = TinyTemplate::new();
+        tt.add_template("serve_compile_dir.html", TEMPLATE_SERVE_COMPILE_DIR)?;
+        let html = tt.render("serve_compile_dir.html", &context)?;
+        self.outputs.insert(Path::new(dir).join("index.html"), html);
+        Ok(())
+    }
+
+    /// Metadata and payload of the envelope on `lineno`, the payload split into lines the same
+    /// way a parse does
+    pub fn envelope(&self, lineno: usize) -> anyhow::Result<(serde_json::Value, String)> {
+        let i = self
+            .spans
+            .binary_search_by_key(&lineno, |span| span.lineno)
+            .map_err(|_| {
+                anyhow::anyhow!("{} has no envelope on line {lineno}", self.log.display())
+            })?;
+        let mut reader = BufReader::new(File::open(&self.log)?);
+        let lines = crate::read_spans(&mut reader, std::slice::from_ref(&self.spans[i]))?;
+        let metadata = lines
+            .first()
+            .and_then(|(_, line)| envelope_json(line))
+            .with_context(|| format!("Line {lineno} isn't an envelope"))?;
+        let payload: Vec<&str> = lines
+            .iter()
+            .skip(1)
+            .map_while(|(_, line)| line.strip_prefix('\t'))
+            .collect();
+        Ok((serde_json::from_str(metadata)?, payload.join("\n")))
+    }
+}
+
+// The JSON of an envelope, after its glog prefix
+fn envelope_json(line: &str) -> Option<&str> {
+    line.split_once("] ").map(|(_, json)| json)
+}
+
+// The compile directory whose parse produces `path`: the directory it's in, or the one a
+// provenance tracking page is about
+fn compile_dir_of(path: &Path) -> Option<String> {
+    let mut components = path.components();
+    let first = components.next()?.as_os_str().to_str()?;
+    if components.next().is_some() {
+        return Some(first.to_string());
+    }
+    first
+        .strip_prefix("provenance_tracking_")?
+        .strip_suffix(".html")
+        .map(str::to_string)
+}
+
+fn percent_decode(s: &str) -> String {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut i = 0;
+    while i < s.len() {
+        let decoded = (s.as_bytes()[i] == b'%')
+            .then(|| s.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match decoded {
+            Some(byte) => {
+                bytes.push(byte);
+                i += 3;
+            }
+            None => {
+                bytes.push(s.as_bytes()[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+// Output path a request URL refers to
+fn request_path(url: &str) -> PathBuf {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let mut path = percent_decode(path.trim_start_matches('/'));
+    if path.is_empty() || path.ends_with('/') {
+        path.push_str("index.html");
+    }
+    PathBuf::from(path)
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("json") => "application/json",
+        Some("js") => "text/javascript; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        _ => "text/plain; charset=utf-8",
+    }
+}
+
+pub struct Server<'c> {
+    server: tiny_http::Server,
+    report: LazyReport<'c>,
+}
+
+impl<'c> Server<'c> {
+    /// Listen on `port` of localhost; port 0 picks a free one
+    pub fn bind(report: LazyReport<'c>, port: u16) -> anyhow::Result<Self> {
+        let server = tiny_http::Server::http(("127.0.0.1", port))
+            .map_err(|e| anyhow::anyhow!("Couldn't listen on port {port}: {e}"))?;
+        Ok(Server { server, report })
+    }
+
+    pub fn url(&self) -> String {
+        match self.server.server_addr().to_ip() {
+            Some(addr) => format!("http://{addr}/"),
+            None => "http://127.0.0.1/".to_string(),
+        }
+    }
+
+    /// Answer requests until the process is killed
+    pub fn run(mut self) -> anyhow::Result<()> {
+        loop {
+            self.answer_next()?;
+        }
+    }
+
+    /// Wait for the next request and answer it
+    pub fn answer_next(&mut self) -> anyhow::Result<()> {
+        let request = self.server.recv()?;
+        let path = request_path(request.url());
+        let content = self.report.get(&path);
+        let response = match content {
+            Ok(Some(content)) => tiny_http::Response::from_string(content).with_header(
+                tiny_http::Header::from_bytes("Content-Type", content_type(&path)).unwrap(),
+            ),
+            Ok(None) => tiny_http::Response::from_string(format!(
+                "{} is not part of this report",
+                path.display()
+            ))
+            .with_status_code(404),
+            Err(err) => {
+                eprintln!("Failed to render {}: {err:#}", path.display());
+                tiny_http::Response::from_string(format!("{err:#}")).with_status_code(500)
+            }
+        };
+        if let Err(err) = request.respond(response) {
+            eprintln!("Failed to answer request for {}: {err}", path.display());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+// Viewer for a --single-file report.  Every output file is embedded gzipped and base64'd in
+// #tlparse-files; the one being viewed is decoded on demand and shown in #tlparse-view.  The
+// location hash holds the path of the file being viewed (plus an optional #anchor inside it),
+// so back/forward and deep links keep working.
+(function () {
+    const files = JSON.parse(document.getElementById('tlparse-files').textContent);
+    const view = document.getElementById('tlparse-view');
+    const decoded = new Map();
+    // Relative links are resolved against this fake origin to find the bundled path
+    const origin = 'http://tlparse.invalid';
+    let current = 'index.html';
+
+    function decode(path) {
+        if (!decoded.has(path)) {
+            const bytes = Uint8Array.from(atob(files[path]), c => c.charCodeAt(0));
+            const stream = new Blob([bytes]).stream().pipeThrough(new DecompressionStream('gzip'));
+            decoded.set(path, new Response(stream).text());
+        }
+        return decoded.get(path);
+    }
+
+    function escapeHtml(text) {
+        return text.replace(/&/g, '&amp;').replace(/</g, '&lt;').replace(/>/g, '&gt;');
+    }
+
+    // Scripts a page loads from the report, like search_index.js, are inlined since the srcdoc
+    // frame can't fetch them
+    async function inlineScripts(html, path) {
+        let inlined = '';
+        let last = 0;
+        for (const match of html.matchAll(/<script src="([^"]+)"><\/script>/g)) {
+            const url = new URL(match[1], origin + '/' + path);
+            const src = decodeURIComponent(url.pathname.slice(1));
+            if (url.origin !== origin || !(src in files)) continue;
+            const script = (await decode(src)).replace(/<\/script/gi, '<\\/script');
+            inlined += html.slice(last, match.index) + '<script>' + script + '<\/script>';
+            last = match.index + match[0].length;
+        }
+        return inlined + html.slice(last);
+    }
+
+    function parseHash() {
+        const hash = decodeURIComponent(window.location.hash.slice(1));
+        const split = hash.indexOf('#');
+        if (split === -1) {
+            return [hash || 'index.html', ''];
+        }
+        return [hash.slice(0, split) || 'index.html', hash.slice(split + 1)];
+    }
+
+    function onLinkClick(event) {
+        const link = event.target.closest && event.target.closest('a[href]');
+        if (!link) return;
+        const href = link.getAttribute('href');
+        if (href.startsWith('#')) return;
+        const url = new URL(href, origin + '/' + current);
+        event.preventDefault();
+        if (url.origin !== origin) {
+            window.open(url.href, '_blank');
+            return;
+        }
+        const path = decodeURIComponent(url.pathname.slice(1));
+        window.location.hash = path + url.hash;
+    }
+
+    async function show() {
+        const [path, anchor] = parseHash();
+        current = path;
+        document.title = path;
+        let html;
+        if (!(path in files)) {
+            html = '<p>' + escapeHtml(path) + ' is not part of this report</p>';
+        } else if (path.endsWith('.html')) {
+            html = await inlineScripts(await decode(path), path);
+        } else {
+            html = '<pre>' + escapeHtml(await decode(path)) + '</pre>';
+        }
+        view.onload = function () {
+            view.contentDocument.addEventListener('click', onLinkClick);
+            if (anchor) {
+                view.contentWindow.location.hash = anchor;
+            }
+        };
+        view.srcdoc = html;
+    }
+
+    window.addEventListener('hashchange', show);
+    show();
+})();
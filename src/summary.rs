@@ -0,0 +1,342 @@
+//! `tlparse summary`: a short Markdown report of a parse, for pasting into an issue or chat
+//! when the HTML report can't be opened (e.g. on a remote server).  It's built from the
+//! machine readable outputs, like `parse_summary`, so it only needs a `ParseOutput`.
+
+use fxhash::FxHashMap;
+use regex::Regex;
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::types::{CompilationMetricsMetadata, FxIndexMap, ParseOutput};
+
+// Rows per table; the rest are counted in a line below it
+const MAX_COMPILE_IDS: usize = 30;
+const MAX_ROWS: usize = 10;
+// Reasons are put on one line and cut to this many characters
+const MAX_REASON_LEN: usize = 120;
+
+#[derive(Default)]
+struct CacheCounts {
+    hits: usize,
+    misses: usize,
+    bypasses: usize,
+}
+
+// Text for a table cell: on one line, shortened, with pipes escaped
+fn cell(text: &str, max_len: usize) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut cell: String = line.chars().take(max_len).collect();
+    if cell.len() < line.len() {
+        cell.push('…');
+    }
+    cell.replace('|', "\\|")
+}
+
+// `co_name (file.py:12)`, with just the file name so rows stay short
+fn frame(m: &CompilationMetricsMetadata) -> String {
+    let name = m.co_name.as_deref().unwrap_or("(unknown)");
+    match &m.co_filename {
+        Some(filename) => {
+            let file = Path::new(filename)
+                .file_name()
+                .map_or(filename.clone(), |f| f.to_string_lossy().to_string());
+            format!("{name} ({file}:{})", m.co_firstlineno.unwrap_or(0))
+        }
+        None => name.to_string(),
+    }
+}
+
+fn seconds(s: Option<f64>) -> String {
+    s.map_or("".to_string(), |s| format!("{s:.2}"))
+}
+
+// Status of a compile id; `retried` is whether there's a later attempt at it
+fn status(m: Option<&CompilationMetricsMetadata>, retried: bool) -> &'static str {
+    match m {
+        // Metrics are only logged for the last attempt
+        None if retried => "restarted",
+        None => "no metrics",
+        Some(m) if m.fail_type.is_some() => "failed",
+        Some(m) if m.restart_reasons.as_ref().is_some_and(|r| !r.is_empty()) => "restarted",
+        Some(_) => "ok",
+    }
+}
+
+fn more(out: &mut String, total: usize, shown: usize) {
+    if total > shown {
+        let _ = writeln!(out, "\n…and {} more", total - shown);
+    }
+}
+
+/// Render a Markdown summary of the output of `parse_path`: compile ids with their status,
+/// the slowest compiles, graph breaks and failures, recompiled frames, cache hit rates and
+/// parse stats.  Long tables are cut short so the result fits in an issue comment.
+pub fn markdown_summary(output: &ParseOutput, title: &str) -> anyhow::Result<String> {
+    let summary = crate::parse_summary(output)?;
+    let mut metrics: FxIndexMap<String, CompilationMetricsMetadata> = FxIndexMap::default();
+    for m in &summary.metrics {
+        let compile_id = m["compile_id"].as_str().unwrap_or_default().to_string();
+        // A compile id can be logged more than once; the last one wins, as in the index
+        metrics.insert(compile_id, serde_json::from_value(m.clone())?);
+    }
+
+    // Cache lookups, from the artifact names; *_hash artifacts carry the state in their payload
+    // and only count when there's no hit/miss/bypass artifact for the same lookup
+    let cache_artifact_re = Regex::new(r"^(\w+)_cache_(hit|miss|bypass|hash)_\d+\.json$")?;
+    let mut cache: FxIndexMap<String, CacheCounts> = FxIndexMap::default();
+    // Compile id to the states of each cache it looked up, in order of first appearance
+    let mut cache_by_compile_id: FxHashMap<&str, FxIndexMap<&str, Vec<&str>>> =
+        FxHashMap::default();
+    let mut lookups = Vec::new();
+    for artifact in &summary.artifacts {
+        let Some(caps) = cache_artifact_re.captures(&artifact.name) else {
+            continue;
+        };
+        let state = if &caps[2] == "hash" {
+            let payload = artifact.content.as_deref().unwrap_or_default();
+            serde_json::from_str::<serde_json::Value>(payload)
+                .ok()
+                .and_then(|v| v["cache_state"].as_str().map(str::to_string))
+                .unwrap_or_default()
+        } else {
+            caps[2].to_string()
+        };
+        lookups.push((
+            artifact.compile_id.as_str(),
+            caps[1].to_string(),
+            state,
+            &caps[2] == "hash",
+        ));
+    }
+    for (compile_id, layer, state, from_hash) in &lookups {
+        if *from_hash
+            && lookups
+                .iter()
+                .any(|(c, l, _, h)| !h && c == compile_id && l == layer)
+        {
+            continue;
+        }
+        let counts = cache.entry(layer.clone()).or_default();
+        match state.as_str() {
+            "hit" => counts.hits += 1,
+            "miss" => counts.misses += 1,
+            "bypass" => counts.bypasses += 1,
+            _ => continue,
+        }
+        let states = cache_by_compile_id
+            .entry(compile_id)
+            .or_default()
+            .entry(layer)
+            .or_default();
+        if !states.contains(&state.as_str()) {
+            states.push(state);
+        }
+    }
+
+    let mut out = String::new();
+    writeln!(out, "# tlparse summary: {title}\n")?;
+
+    // [0/0] is retried as [0/0_1]
+    let retried = |compile_id: &str| {
+        let prefix = format!("{}_", compile_id.trim_end_matches(']'));
+        summary.compile_ids.iter().any(|id| id.starts_with(&prefix))
+    };
+    let mut counts: FxIndexMap<&str, usize> = FxIndexMap::default();
+    for compile_id in &summary.compile_ids {
+        *counts
+            .entry(status(metrics.get(compile_id), retried(compile_id)))
+            .or_default() += 1;
+    }
+    let counts: Vec<String> = counts
+        .iter()
+        .map(|(status, count)| format!("{count} {status}"))
+        .collect();
+    let times: Vec<f64> = metrics
+        .values()
+        .filter_map(|m| m.entire_frame_compile_time_s)
+        .collect();
+    write!(
+        out,
+        "**{} compile ids** ({})",
+        summary.compile_ids.len(),
+        if counts.is_empty() {
+            "none".to_string()
+        } else {
+            counts.join(", ")
+        },
+    )?;
+    if !times.is_empty() {
+        write!(
+            out,
+            ", {:.2}s compiling in total",
+            times.iter().sum::<f64>()
+        )?;
+    }
+    writeln!(out, "\n")?;
+
+    if !summary.compile_ids.is_empty() {
+        writeln!(out, "## Compile ids\n")?;
+        writeln!(
+            out,
+            "| Compile id | Frame | Status | Compile time (s) | Cache |"
+        )?;
+        writeln!(out, "|---|---|---|---|---|")?;
+        for compile_id in summary.compile_ids.iter().take(MAX_COMPILE_IDS) {
+            let m = metrics.get(compile_id);
+            writeln!(
+                out,
+                "| {compile_id} | {} | {} | {} | {} |",
+                m.map(frame).unwrap_or_default(),
+                status(m, retried(compile_id)),
+                seconds(m.and_then(|m| m.entire_frame_compile_time_s)),
+                cache_by_compile_id
+                    .get(compile_id.as_str())
+                    .map(|layers| layers
+                        .iter()
+                        .map(|(layer, states)| format!("{layer} {}", states.join("/")))
+                        .collect::<Vec<_>>()
+                        .join(", "))
+                    .unwrap_or_default(),
+            )?;
+        }
+        more(&mut out, summary.compile_ids.len(), MAX_COMPILE_IDS);
+        writeln!(out)?;
+    }
+
+    let mut slowest: Vec<(&String, &CompilationMetricsMetadata, f64)> = metrics
+        .iter()
+        .filter_map(|(id, m)| Some((id, m, m.entire_frame_compile_time_s?)))
+        .collect();
+    slowest.sort_by(|a, b| b.2.total_cmp(&a.2));
+    if !slowest.is_empty() {
+        writeln!(out, "## Slowest compiles\n")?;
+        writeln!(
+            out,
+            "| Compile id | Frame | Compile time (s) | Backend (s) | Inductor (s) |"
+        )?;
+        writeln!(out, "|---|---|---|---|---|")?;
+        for (compile_id, m, time) in slowest.iter().take(MAX_ROWS) {
+            writeln!(
+                out,
+                "| {compile_id} | {} | {time:.2} | {} | {} |",
+                frame(m),
+                seconds(m.backend_compile_time_s),
+                seconds(m.inductor_compile_time_s),
+            )?;
+        }
+        more(&mut out, slowest.len(), MAX_ROWS);
+        writeln!(out)?;
+    }
+
+    if !summary.failures.is_empty() {
+        writeln!(out, "## Graph breaks and failures\n")?;
+        writeln!(out, "| Compile id | Type | Reason | User location |")?;
+        writeln!(out, "|---|---|---|---|")?;
+        for failure in summary.failures.iter().take(MAX_ROWS) {
+            // Restarts don't record where they happened; the frame being compiled is close
+            let location = failure.user_frame.clone().or_else(|| {
+                let m = metrics.get(&failure.compile_id)?;
+                Some(format!(
+                    "{}:{}",
+                    m.co_filename.as_deref()?,
+                    m.co_firstlineno.unwrap_or(0)
+                ))
+            });
+            writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                failure.compile_id,
+                failure.failure_type,
+                cell(&failure.reason, MAX_REASON_LEN),
+                location.map_or("".to_string(), |l| format!("`{}`", cell(&l, usize::MAX))),
+            )?;
+        }
+        more(&mut out, summary.failures.len(), MAX_ROWS);
+        writeln!(out)?;
+    }
+
+    // Compile ids of a frame share everything up to the last /, e.g. [0/0], [0/1] and [0/1_1];
+    // retries of the same compile don't count as recompiles
+    let mut frames: FxIndexMap<&str, Vec<&String>> = FxIndexMap::default();
+    for compile_id in &summary.compile_ids {
+        if retried(compile_id) {
+            continue;
+        }
+        if let Some((frame, _)) = compile_id.rsplit_once('/') {
+            frames.entry(frame).or_default().push(compile_id);
+        }
+    }
+    let mut recompiled: Vec<(&str, Vec<&String>)> = frames
+        .into_iter()
+        .filter(|(_, compile_ids)| compile_ids.len() > 1)
+        .collect();
+    recompiled.sort_by_key(|(_, compile_ids)| std::cmp::Reverse(compile_ids.len()));
+    if !recompiled.is_empty() {
+        writeln!(out, "## Recompiles\n")?;
+        writeln!(out, "| Frame | Compiles | Compile ids |")?;
+        writeln!(out, "|---|---|---|")?;
+        for (frame_id, compile_ids) in recompiled.iter().take(MAX_ROWS) {
+            let ids: Vec<&str> = compile_ids.iter().map(|id| id.as_str()).collect();
+            let name = compile_ids
+                .iter()
+                .find_map(|id| metrics.get(*id))
+                .map_or(format!("{frame_id}]"), |m| {
+                    format!("{frame_id}] {}", frame(m))
+                });
+            writeln!(
+                out,
+                "| {name} | {} | {} |",
+                compile_ids.len(),
+                cell(&ids.join(" "), MAX_REASON_LEN)
+            )?;
+        }
+        more(&mut out, recompiled.len(), MAX_ROWS);
+        writeln!(out)?;
+    }
+
+    if !cache.is_empty() {
+        writeln!(out, "## Cache\n")?;
+        writeln!(out, "| Cache | Hits | Misses | Bypasses | Hit rate |")?;
+        writeln!(out, "|---|---|---|---|---|")?;
+        for (layer, c) in &cache {
+            let total = c.hits + c.misses + c.bypasses;
+            writeln!(
+                out,
+                "| {layer} | {} | {} | {} | {:.1}% |",
+                c.hits,
+                c.misses,
+                c.bypasses,
+                100.0 * c.hits as f64 / total.max(1) as f64
+            )?;
+        }
+        writeln!(out)?;
+    }
+
+    if let Some((_, stats)) = output
+        .iter()
+        .find(|(path, _)| path == Path::new("parse_stats.json"))
+    {
+        let stats: serde_json::Map<String, serde_json::Value> = serde_json::from_str(stats)?;
+        let fields: Vec<String> = stats
+            .iter()
+            .filter_map(|(name, value)| match value {
+                serde_json::Value::Number(n) if n.as_u64() != Some(0) => {
+                    Some(format!("{name}: {n}"))
+                }
+                serde_json::Value::Array(errors) => {
+                    let errors: Vec<String> = errors
+                        .iter()
+                        .filter_map(|e| {
+                            Some(format!("{}/{}: {}", e[0].as_str()?, e[1].as_str()?, e[2]))
+                        })
+                        .collect();
+                    (!errors.is_empty()).then(|| format!("parser errors {}", errors.join(", ")))
+                }
+                _ => None,
+            })
+            .collect();
+        writeln!(out, "## Parse stats\n")?;
+        writeln!(out, "{}", fields.join(", "))?;
+    }
+    Ok(out)
+}
@@ -2,17 +2,21 @@ pub static CSS: &str = r#"
 table td { vertical-align: top; }
 
 .stack-trie { white-space: nowrap; font-family: monospace; }
+li.group { font-weight: bold; list-style-type: none; }
+li.grouped { margin-left: 2ch; }
 .stack-trie ul { padding-left: 1ch;  }
 .stack-trie li { margin-left: 1ch; list-style-type: none;  }
-.stack-trie .marker {
+.stack-trie .marker, .compile-id .marker {
   cursor: pointer;
 }
-.stack-trie .marker.collapsed::before {
+.stack-trie .marker.collapsed::before, .compile-id .marker.collapsed::before {
   content: "+ ";
 }
-.stack-trie .marker:not(.collapsed)::before {
+.stack-trie .marker:not(.collapsed)::before, .compile-id .marker:not(.collapsed)::before {
   content: "- ";
 }
+.compile-id { list-style-type: none; }
+.compile-id-summary { color: #666; }
 .stack-trie a { text-decoration: none; }
 .stack-trie a:hover { text-decoration: underline; }
 .status-missing { background-color: purple; color: white; }
@@ -105,9 +109,24 @@ pub static TEMPLATE_INDEX: &str = r#"
 <script>
 {javascript | format_unescaped}
 </script>
+<script src="search_index.js"></script>
+<script>
+{search_js | format_unescaped}
+</script>
+<script>
+{compile_ids_js | format_unescaped}
+</script>
 <body>
 <div>
 {custom_header_html | format_unescaped}
+<h2>Search</h2>
+<p>
+Find the compile ids whose graphs, guards, generated code or failure reasons mention something, e.g.
+an op name.
+</p>
+<input id="search-input" type="search" placeholder="aten.foo" size="40">
+<select id="search-kind"><option value="">All types</option></select>
+<div id="search-results"></div>
 <h2>Stack trie</h2>
 <p>
 The <strong>stack trie</strong> is a way of getting a quick orientation on where all the
@@ -179,6 +198,82 @@ phase generates:
 <li>Inductor will perform code generation, producing the final <code>inductor_output_code</code> which will be executed at runtime.  This output is a valid Python program and can be directly run.</li>
 </ol>
 
+{{ if num_guard_diffs }}
+<h2> Guard Diffs </h2>
+<p>
+Frames in this run were recompiled <strong><a href="guard_diff.html">{num_guard_diffs} time(s)</a></strong>.
+The guard diff shows which guards were added, removed or changed between consecutive compiles of a frame, next to the recompile reasons.
+</p>
+{{ endif }}
+{{ if has_cache_report }}
+<h2> Compile Cache </h2>
+<p>
+The <a href="cache_report.html">cache report</a> shows hit rates of the FX graph and AOTAutograd caches, why lookups were bypassed,
+and which cache key components changed for each miss.
+</p>
+{{ endif }}
+{{ if has_kernels }}
+<h2> Kernels </h2>
+<p>
+The <a href="kernels.html">kernel inventory</a> lists every kernel generated or called by Inductor in this run, and the compiles
+that did not produce any Triton kernels.
+</p>
+{{ endif }}
+{{ if parser_outputs }}
+<h2> Parser Outputs </h2>
+<p>
+Run-wide outputs produced by parsers after the whole log was processed.
+</p>
+<ul>
+{{ for out in parser_outputs }}
+<li><a href="{out.1}">{out.0}</a></li>
+{{ endfor }}
+</ul>
+{{ endif }}
+{{ if parser_errors }}
+<h2> Parser Errors </h2>
+<p>
+Some parsers failed; their outputs may be missing. Failures by parser and kind:
+</p>
+<table>
+<tr> <th>Parser</th> <th>Kind</th> <th>Count</th> </tr>
+{{ for err in parser_errors }}
+<tr> <td>{err.0}</td> <td>{err.1}</td> <td>{err.2}</td> </tr>
+{{ endfor }}
+</table>
+{{ endif }}
+{{ if unknown_fields }}
+<h2> Unknown Log Types </h2>
+<p>
+These structured log types are not recognized by this version of tlparse. Their metadata and
+payloads are rendered as <code>unknown_*</code> files in the compile directories above.
+</p>
+<ul>
+{{ for field in unknown_fields }}
+<li><code>{field.0}</code> ({field.1} occurrences)</li>
+{{ endfor }}
+</ul>
+{{ endif }}
+{{ if has_feature_usage }}
+<h2> Feature Usage </h2>
+<p>
+The <a href="feature_usage.html">feature usage table</a> shows which Dynamo and Inductor features were exercised by each compile.
+</p>
+{{ endif }}
+{{ if has_autotuning }}
+<h2> Autotuning </h2>
+<p>
+The <a href="autotuning.html">autotuning report</a> shows the ops each compile autotuned and the choices that won,
+the configs of its Triton kernels, and which ops dominate autotuning time.
+</p>
+{{ endif }}
+{{ if has_dynamic_shapes }}
+<h2> Dynamic Shapes </h2>
+<p>
+The <a href="dynamic_shapes.html">dynamic shapes dashboard</a> lists every symbol created during this run, its sources and value ranges,
+the compiles it appears in, and every specialization and guard on symbolic shapes.
+</p>
+{{ endif }}
 {{ if has_chromium_events }}
 <h2> Chromium Events </h2>
 PT2 generates <a href='chromium_events.json'>Chromium Trace Events</a> in JSON on specific events during compilation.
@@ -188,14 +283,29 @@ You can download and view them in a tool like <a href='https://ui.perfetto.dev/'
 <a href="collectives_parity.json">Collectives Parity report</a> comparing scheduler and Inductor output code collective operations.
 </p>
 <p>
-Build products below:
+Build products below, colored by status like the stack trie:
 </p>
-<ul>
+<p>
+Show <select id="compile-id-filter">
+<option value="">all compile ids</option>
+<option value="failure">only failures</option>
+<option value="break">only restarts and graph breaks</option>
+<option value="miss">only cache misses</option>
+</select>
+sorted by <select id="compile-id-sort">
+<option value="">log order</option>
+<option value="compile-time">compile time, slowest first</option>
+<option value="start-time">start time</option>
+</select>
+<label><input type="checkbox" id="compile-id-collapse"> collapse artifacts</label>
+<span id="compile-id-count"></span>
+</p>
+<ul id="compile-ids">
 {{ for compile_directory in directory }}
-    <li><a id="{compile_directory.0}">{compile_directory.0}</a>
+    <li class="compile-id" data-status="{compile_directory.2.status}" data-start-time="{compile_directory.2.start_time}" data-compile-time="{compile_directory.2.compile_time_s}" data-op-count="{compile_directory.2.graph_op_count}" data-cache="{compile_directory.2.cache_status}" data-rank="{compile_directory.2.rank}"><span onclick='toggleList(this)' class='marker'></span><a id="{compile_directory.0}" class="{compile_directory.2.status}">{compile_directory.0}</a>{{ if compile_directory.2.description }} <span class="compile-id-summary">{compile_directory.2.description}</span>{{ endif }}
     <ul>
         {{ for path_idx in compile_directory.1 }}
-            <li><a href="{path_idx.url}">{path_idx.name}</a>{{ if path_idx.readable_url }} (<a href="{path_idx.readable_url}">readable_html</a>){{ endif }} {path_idx.suffix} ({path_idx.number})</li>
+            {{ if path_idx.group_start }}<li class="group">{path_idx.group}</li>{{ endif }}<li{{ if path_idx.group }} class="grouped"{{ endif }}><a href="{path_idx.url}">{path_idx.name}</a>{{ if path_idx.readable_url }} (<a href="{path_idx.readable_url}">readable_html</a>){{ endif }} {path_idx.suffix} ({path_idx.number})</li>
         {{ endfor }}
     </ul>
     </li>
@@ -278,6 +388,288 @@ pub static TEMPLATE_FAILURES_AND_RESTARTS: &str = r#"
 </html>
 "#;
 
+pub static TEMPLATE_GUARD_DIFF: &str = r#"
+<html>
+<head>
+    <style>
+    {css}
+    .guard-added \{ background-color: #e6ffed; }
+    .guard-removed \{ background-color: #ffeef0; }
+    .guard-changed \{ background-color: #fff5b1; }
+    </style>
+    <title>Guard Diffs</title>
+</head>
+<body>
+    <h1>Guard Diffs</h1>
+    <p>
+    Each section compares the guards of a compile against the previous compile of the same frame.
+    Guards are collected from <code>dynamo_guards</code> and <code>dynamo_cpp_guards_str</code>.
+    A guard that was removed and re-added on the same subject (e.g. a tensor check on the same
+    input) is shown as changed.
+    </p>
+    {{ for diff in diffs }}
+    <h2 id="{diff.compile_id}">{diff.prev_compile_id} &rarr; {diff.compile_id}</h2>
+    {{ if diff.recompile_reasons }}
+    <p>Recompile reasons:</p>
+    <ul>
+    {{ for reason in diff.recompile_reasons }}
+        <li><code>{reason}</code></li>
+    {{ endfor }}
+    </ul>
+    {{ endif }}
+    <p>{diff.num_unchanged} guard(s) unchanged.</p>
+    <table>
+    <tr> <th> Change </th> <th> Kind </th> <th> Guard </th> </tr>
+    {{ for g in diff.changed }}
+    <tr class="guard-changed"> <td> changed </td> <td> {g.kind} </td> <td><code>{g.before}</code><br>&rarr; <code>{g.after}</code></td> </tr>
+    {{ endfor }}
+    {{ for g in diff.added }}
+    <tr class="guard-added"> <td> added </td> <td> {g.kind} </td> <td><code>{g.code}</code></td> </tr>
+    {{ endfor }}
+    {{ for g in diff.removed }}
+    <tr class="guard-removed"> <td> removed </td> <td> {g.kind} </td> <td><code>{g.code}</code></td> </tr>
+    {{ endfor }}
+    </table>
+    {{ endfor }}
+    {qps | format_unescaped}
+</body>
+</html>
+"#;
+
+pub static TEMPLATE_AUTOTUNING: &str = r#"
+<html>
+<head>
+    <style>
+    {css}
+    </style>
+    <title>Autotuning</title>
+</head>
+<body>
+    <h1>Autotuning</h1>
+    <p>
+    {{ if autotune_time_s }}The compilation metrics count {autotune_time_s} s of autotuning.{{ endif }}
+    {{ if ops }}Autotuning ops took {op_time_s} s.{{ endif }}
+    {{ if num_benchmarks }}Inductor benchmarked {num_benchmarks} time(s), for {benchmark_time_s} s.{{ endif }}
+    {{ if kernels }}Compiling Triton kernels took {kernel_time_s} s, which is not part of the autotuning time.{{ endif }}
+    </p>
+    {{ if ops }}
+    <h2>Ops by autotune time</h2>
+    <table>
+    <tr> <th> Op </th> <th> Autotunings </th> <th> Autotune time (s) </th> <th> Share </th> </tr>
+    {{ for op in ops }}
+    <tr> <td><code>{op.name}</code></td> <td> {op.count} </td> <td> {op.time_s} </td> <td> {op.share} </td> </tr>
+    {{ endfor }}
+    </table>
+    {{ endif }}
+    {{ if kernels }}
+    <h2>Triton kernels by compile time</h2>
+    <table>
+    <tr> <th> Kernel </th> <th> Compiles </th> <th> Compile time (s) </th> <th> Share </th> </tr>
+    {{ for k in kernels }}
+    <tr> <td><code>{k.name}</code></td> <td> {k.count} </td> <td> {k.time_s} </td> <td> {k.share} </td> </tr>
+    {{ endfor }}
+    </table>
+    {{ endif }}
+    {{ for compile in compiles }}
+    <h2 id="{compile.compile_id}">{compile.compile_id}</h2>
+    <p>
+    {{ if compile.autotune_time_s }}Autotuning: {compile.autotune_time_s} s. {{ endif }}
+    {{ if compile.runtime_autotune_time_s }}Autotuning on the first run: {compile.runtime_autotune_time_s} s. {{ endif }}
+    {{ if compile.num_benchmarks }}Benchmarked {compile.num_benchmarks} time(s), for {compile.benchmark_time_ms} ms.{{ endif }}
+    </p>
+    {{ if compile.ops }}
+    <p>
+    Inductor logs the winning choice and the fastest Triton choice of each op, not the time of every choice.
+    </p>
+    <table>
+    <tr> <th> Op </th> <th> Input shapes </th> <th> Input dtypes </th> <th> Choices </th> <th> Winner </th> <th> Winner time (ms) </th> <th> Fastest Triton choice </th> <th> Autotune time (ms) </th> </tr>
+    {{ for op in compile.ops }}
+    <tr> <td><code>{op.op}</code></td> <td> {op.input_shapes} </td> <td> {op.input_dtypes} </td> <td> {op.num_choices}{{ if op.num_triton_choices }} ({op.num_triton_choices} Triton){{ endif }} </td> <td><code>{op.winner}</code>{{ if op.winner_desc }}<br>{op.winner_desc}{{ endif }}</td> <td> {op.winner_time_ms} </td> <td>{{ if op.best_triton }}<code>{op.best_triton}</code> ({op.best_triton_time_ms} ms){{ if op.best_triton_desc }}<br>{op.best_triton_desc}{{ endif }}{{ endif }}</td> <td> {op.time_ms} </td> </tr>
+    {{ endfor }}
+    </table>
+    {{ endif }}
+    {{ if compile.kernels }}
+    <table>
+    <tr> <th> Kernel </th> <th> Autotune cache </th> <th> Configs </th> <th> Best config </th> <th> Compile time (ms) </th> </tr>
+    {{ for k in compile.kernels }}
+    <tr> <td><code>{k.name}</code></td> <td> {k.cache_state} </td> <td> {k.num_configs} </td> <td>{{ if k.best_config }}<code>{k.best_config}</code>{{ endif }}</td> <td> {k.time_ms} </td> </tr>
+    {{ endfor }}
+    </table>
+    {{ endif }}
+    {{ endfor }}
+    {qps | format_unescaped}
+</body>
+</html>
+"#;
+
+pub static TEMPLATE_KERNELS: &str = r#"
+<html>
+<head>
+    <style>
+    {css}
+    .fallback \{ background-color: #fff5b1; }
+    </style>
+    <title>Kernels</title>
+</head>
+<body>
+    <h1>Kernels</h1>
+    <p>
+    Kernels are collected from <code>inductor_output_code</code>.  Triton kernels with identical source are listed once
+    ({num_definitions} definitions in total), with every compile id that generated them.  Autotuning information comes
+    from <code>triton_kernel_info</code>.
+    </p>
+    <table>
+    <tr> <th> Kernel </th> <th> Kind </th> <th> Compile ids </th> <th> Source nodes </th> <th> Original ATen </th> <th> Autotuning </th> <th> Content hash </th> </tr>
+    {{ for k in kernels }}
+    <tr>
+        <td><code>{k.name}</code></td>
+        <td>{k.kind}</td>
+        <td>{{ for cid in k.compile_ids }}{cid} {{ endfor }}</td>
+        <td>{{ for n in k.source_nodes }}{n} {{ endfor }}</td>
+        <td>{{ for n in k.aten_nodes }}{n} {{ endfor }}</td>
+        <td>{{ for a in k.autotune }}{a}<br>{{ endfor }}</td>
+        <td><code>{k.content_hash}</code></td>
+    </tr>
+    {{ endfor }}
+    </table>
+    {{ if fallback_compiles }}
+    <h2>Compiles without Triton kernels</h2>
+    <p>The output code of these compiles did not define any Triton kernel, i.e. everything fell back to extern kernels or eager.</p>
+    <table>
+    <tr> <th> Compile id </th> <th> Extern kernels </th> </tr>
+    {{ for f in fallback_compiles }}
+    <tr class="fallback"> <td> {f.compile_id} </td> <td>{{ for k in f.extern_kernels }}<code>{k}</code> {{ endfor }}</td> </tr>
+    {{ endfor }}
+    </table>
+    {{ endif }}
+    {qps | format_unescaped}
+</body>
+</html>
+"#;
+
+pub static TEMPLATE_CACHE_REPORT: &str = r#"
+<html>
+<head>
+    <style>
+    {css}
+    </style>
+    <title>Cache Report</title>
+</head>
+<body>
+    <h1>Cache Report</h1>
+    <h2>Hit rates</h2>
+    <table>
+    <tr> <th> Cache </th> <th> Hits </th> <th> Misses </th> <th> Bypasses </th> <th> Hit rate </th> </tr>
+    {{ for l in layers }}
+    <tr> <td> {l.layer} </td> <td> {l.hits} </td> <td> {l.misses} </td> <td> {l.bypasses} </td> <td> {l.hit_rate} </td> </tr>
+    {{ endfor }}
+    </table>
+    {{ if bypass_reasons }}
+    <h2>Bypass reasons</h2>
+    <table>
+    <tr> <th> Cache </th> <th> Reason </th> <th> Count </th> </tr>
+    {{ for b in bypass_reasons }}
+    <tr> <td> {b.layer} </td> <td><pre>{b.reason}</pre></td> <td> {b.count} </td> </tr>
+    {{ endfor }}
+    </table>
+    {{ endif }}
+    {{ if misses }}
+    <h2>Misses</h2>
+    <p>
+    Each miss is compared with the previous lookup in the same cache for the same frame.  Components are
+    shown by value when short, and by hash otherwise.
+    </p>
+    {{ for m in misses }}
+    <h3 id="{m.layer}-{m.compile_id}">{m.compile_id} {m.layer} miss</h3>
+    <p>Key: <code>{m.key}</code></p>
+    {{ if m.prev_compile_id }}
+    {{ if m.changed }}
+    <table>
+    <tr> <th> Component (compared with {m.prev_compile_id}) </th> <th> Before </th> <th> After </th> </tr>
+    {{ for c in m.changed }}
+    <tr> <td><code>{c.name}</code></td> <td><code>{c.before}</code></td> <td><code>{c.after}</code></td> </tr>
+    {{ endfor }}
+    </table>
+    {{ else }}
+    <p>No key components changed since {m.prev_compile_id}; the entry was not found in the cache (e.g. a cold cache or an evicted entry).</p>
+    {{ endif }}
+    {{ else }}
+    <p>First lookup of this frame in this run.</p>
+    {{ endif }}
+    {{ endfor }}
+    {{ endif }}
+    {qps | format_unescaped}
+</body>
+</html>
+"#;
+
+pub static TEMPLATE_DYNAMIC_SHAPES: &str = r#"
+<html>
+<head>
+    <style>
+    {css}
+    .unbacked \{ background-color: #fff5b1; }
+    </style>
+    <title>Dynamic Shapes</title>
+</head>
+<body>
+    <h1>Dynamic Shapes</h1>
+    <p>
+    Symbols are created from <code>create_symbol</code> and <code>create_unbacked_symbol</code>, and
+    are also listed when they are only known from a specialization.  Every compile has its own
+    symbols, even when they have the same name as another compile's, so they are listed per compile
+    id, each with the expressions, specializations and guards of that compile that mention it.
+    </p>
+    <h2>Symbols</h2>
+    <p>{num_unbacked} of the symbols below are unbacked.</p>
+    <table>
+    <tr> <th> Compile id </th> <th> Symbol </th> <th> Sources </th> <th> Value </th> <th> Value range </th> <th> User stack </th> <th> Uses </th> </tr>
+    {{ for s in symbols }}
+    <tr{{ if s.unbacked }} class="unbacked"{{ endif }}>
+        <td>{s.compile_id}</td>
+        <td><code>{s.symbol}</code>{{ if s.unbacked }} (unbacked){{ endif }}</td>
+        <td>{{ for source in s.sources }}<code>{source}</code><br>{{ endfor }}</td>
+        <td>{s.value}</td>
+        <td>{s.value_range}</td>
+        <td>{s.user_stack_html | format_unescaped}</td>
+        <td>{{ for use in s.uses }}{use.kind}: <code>{use.expr}</code><br>{{ endfor }}</td>
+    </tr>
+    {{ endfor }}
+    </table>
+    {{ if specializations }}
+    <h2>Specializations</h2>
+    <table>
+    <tr> <th> Compile id </th> <th> Symbol </th> <th> Sources </th> <th> Value </th> <th> Reason </th> <th> User stack </th> </tr>
+    {{ for spec in specializations }}
+    <tr>
+        <td>{spec.compile_id}</td>
+        <td><code>{spec.symbol}</code></td>
+        <td>{{ for source in spec.sources }}<code>{source}</code><br>{{ endfor }}</td>
+        <td>{spec.value}</td>
+        <td>{spec.reason}</td>
+        <td>{spec.user_stack_html | format_unescaped}</td>
+    </tr>
+    {{ endfor }}
+    </table>
+    {{ endif }}
+    {{ if guards }}
+    <h2>Guards added</h2>
+    <table>
+    <tr> <th> Compile id </th> <th> Guard </th> <th> User stack </th> </tr>
+    {{ for g in guards }}
+    <tr>
+        <td>{g.compile_id}</td>
+        <td><code>{g.expr}</code></td>
+        <td>{g.user_stack_html | format_unescaped}</td>
+    </tr>
+    {{ endfor }}
+    </table>
+    {{ endif }}
+    {qps | format_unescaped}
+</body>
+</html>
+"#;
+
 pub static TEMPLATE_COMPILATION_METRICS: &str = r#"
 <html>
 <head>
@@ -296,6 +688,9 @@ pub static TEMPLATE_COMPILATION_METRICS: &str = r#"
             <li><a href="{compile_id_dir}/{path_idx.url}">{path_idx.name}</a> ({path_idx.number})</li>
         {{ endfor }}
     </ul>
+    {{ if tensor_inputs_url }}
+    <p><a href="{compile_id_dir}/{tensor_inputs_url}">Graph inputs</a></p>
+    {{ endif }}
     <h2>Stack</h2>
     {stack_html | format_unescaped}
     <h2>Compile Time(seconds)</h2>
@@ -376,6 +771,126 @@ pub static TEMPLATE_COMPILATION_METRICS: &str = r#"
     </tr>
     {{ endfor }}
     </table>
+    {{ for r in runtime_metrics }}
+    {{ call runtime_metrics_table.html with r }}
+    {{ endfor }}
+    {qps | format_unescaped}
+</body>
+</html>
+"#;
+
+pub static TEMPLATE_TENSOR_INPUTS_CSS: &str = r#"
+table {
+    border-collapse: collapse;
+    margin: 20px 0;
+}
+table, th, td {
+    border: 1px solid #999;
+    padding: 6px 10px;
+    text-align: left;
+    font-family: monospace;
+}
+th {
+    background-color: #d3d3d3;
+}
+.symbolic {
+    background-color: #ffe08a;
+    font-weight: bold;
+}
+tr.dynamic td:first-child {
+    border-left: 4px solid #f0a000;
+}
+"#;
+
+pub static TEMPLATE_TENSOR_INPUTS: &str = r#"
+<html>
+<head>
+    <style>
+    {css}
+    </style>
+    <title>Tensor Inputs</title>
+</head>
+<body>
+    <h1>Tensor inputs for {compile_id}</h1>
+    <p>
+    Tensors described by Dynamo while compiling this frame, joined with the storages and
+    sources they were described with.  <span class="symbolic">Highlighted</span> sizes are
+    symbolic or were marked dynamic.
+    </p>
+    <table>
+    <tr>
+        <th>Id</th> <th>Source(s)</th> <th>Dtype</th> <th>Device</th> <th>Size</th> <th>Stride</th>
+        <th>Storage</th> <th>requires_grad</th> <th>Leaf</th> <th>Parameter</th> <th>View</th>
+    </tr>
+    {{ for t in inputs }}
+    <tr{{ if t.is_dynamic }} class="dynamic"{{ endif }}>
+        <td>{t.id}</td>
+        <td>{{ for source in t.sources }}{source}<br>{{ endfor }}</td>
+        <td>{t.dtype}</td>
+        <td>{t.device}</td>
+        <td>{t.size_html | format_unescaped}</td>
+        <td>{t.stride_html | format_unescaped}</td>
+        <td>{t.storage}</td>
+        <td>{t.requires_grad}</td>
+        <td>{t.is_leaf}</td>
+        <td>{t.is_parameter}</td>
+        <td>{t.view}</td>
+    </tr>
+    {{ endfor }}
+    </table>
+    {qps | format_unescaped}
+</body>
+</html>
+"#;
+
+pub static TEMPLATE_RUNTIME_METRICS_TABLE: &str = r#"
+    <h2>Runtime Metrics</h2>
+    <table>
+    <tr> <th> Metric </th> <th> Value </th> </tr>
+    {{ if is_forward }}<tr> <td> Graph </td> <td> forward </td> </tr>{{ else }}<tr> <td> Graph </td> <td> backward </td> </tr>{{ endif }}
+    {{ if duration_us }}<tr> <td> First run duration (us) </td> <td> {duration_us} </td> </tr>{{ endif }}
+    {{ if runtime_cudagraphify_time_us }}<tr> <td> Cudagraphify time (us) </td> <td> {runtime_cudagraphify_time_us} </td> </tr>{{ endif }}
+    {{ if runtime_triton_autotune_time_us }}<tr> <td> Triton autotune time (us) </td> <td> {runtime_triton_autotune_time_us} </td> </tr>{{ endif }}
+    {{ if start_time_us }}<tr> <td> Start time (us) </td> <td> {start_time_us} </td> </tr>{{ endif }}
+    {{ if cuda_version }}<tr> <td> CUDA version </td> <td> {cuda_version} </td> </tr>{{ endif }}
+    {{ if triton_version }}<tr> <td> Triton version </td> <td> {triton_version} </td> </tr>{{ endif }}
+    {{ if python_version }}<tr> <td> Python version </td> <td> {python_version} </td> </tr>{{ endif }}
+    </table>
+"#;
+
+pub static TEMPLATE_COMPILATION_METRICS_RUNTIME: &str = r#"
+<html>
+<head>
+    <style>
+    {css}
+    </style>
+    <title>Runtime Metrics</title>
+</head>
+<body>
+    <h1>Runtime Metrics for {compile_id}</h1>
+    {{ call runtime_metrics_table.html with m }}
+    {qps | format_unescaped}
+</body>
+</html>
+"#;
+
+pub static TEMPLATE_FEATURE_USAGE: &str = r#"
+<html>
+<head>
+    <style>
+    {css}
+    </style>
+    <title>Feature Usage</title>
+</head>
+<body>
+    <h1>Feature Usage</h1>
+    <p>Dynamo and Inductor features checked during this run, from the <code>feature_usage</code> of the compilation metrics.</p>
+    <table>
+    <tr> <th> Feature </th> <th> Used in </th> <th> Not used in </th> </tr>
+    {{ for f in features }}
+    <tr> <td><code>{f.feature}</code></td> <td>{{ for cid in f.enabled_in }}{cid} {{ endfor }}</td> <td>{{ for cid in f.disabled_in }}{cid} {{ endfor }}</td> </tr>
+    {{ endfor }}
+    </table>
     {qps | format_unescaped}
 </body>
 </html>
@@ -665,3 +1180,177 @@ All ranks have matching tensor meta signatures across graphs.
 </body>
 </html>
 "#;
+
+pub static TEMPLATE_JSON_TABLE: &str = r#"
+<html>
+<head>
+    <style>
+    {css}
+    </style>
+    <title>{title}</title>
+</head>
+<body>
+    <h1>{title}</h1>
+    <table>
+    <tr>{{ for column in columns }} <th>{column}</th>{{ endfor }} </tr>
+    {{ for row in rows }}
+    <tr>{{ for cell in row }} <td>{cell}</td>{{ endfor }} </tr>
+    {{ endfor }}
+    </table>
+    {qps | format_unescaped}
+</body>
+</html>
+"#;
+
+pub static TEMPLATE_MARKDOWN: &str = r#"
+<html>
+<head>
+    <style>
+    {css}
+    </style>
+    <title>{title}</title>
+</head>
+<body>
+{body | format_unescaped}
+{qps | format_unescaped}
+</body>
+</html>
+"#;
+
+pub static TEMPLATE_SERVE_COMPILE_DIR: &str = r#"
+<html>
+<head>
+    <meta charset="UTF-8">
+    <style>
+    {css}
+    </style>
+    <title>{compile_id}</title>
+</head>
+<body>
+<p><a href="/index.html">Back to the index</a></p>
+<h2>{compile_id}</h2>
+<ul>
+{{ for f in files }}
+    {{ if f.group_start }}<li class="group">{f.group}</li>{{ endif }}<li{{ if f.group }} class="grouped"{{ endif }}><a href="{f.url}">{f.name}</a>{{ if f.readable_url }} (<a href="{f.readable_url}">readable_html</a>){{ endif }} {f.suffix} ({f.number})</li>
+{{ endfor }}
+</ul>
+</body>
+</html>
+"#;
+
+pub static SEARCH_JS: &str = include_str!("search.js");
+
+pub static COMPILE_IDS_JS: &str = include_str!("compile_ids.js");
+pub static SINGLE_FILE_JS: &str = include_str!("single_file.js");
+pub static TEMPLATE_SINGLE_FILE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <title>{title}</title>
+    <style>
+    html, body \{ margin: 0; height: 100%; }
+    #tlparse-view \{ border: none; display: block; width: 100%; height: 100%; }
+    </style>
+</head>
+<body>
+<iframe id="tlparse-view"></iframe>
+<script id="tlparse-files" type="application/json">{files | format_unescaped}</script>
+<script>
+{js | format_unescaped}
+</script>
+</body>
+</html>
+"#;
+
+/// Everything `--template-dir` can override, by file name: stylesheets, and TinyTemplate
+/// templates with the context struct (in types.rs) they're rendered with
+pub fn overridable_templates() -> [(&'static str, &'static str, &'static str); 26] {
+    [
+        ("main.css", "", CSS),
+        ("export.css", "", EXPORT_CSS),
+        ("failures.css", "", TEMPLATE_FAILURES_CSS),
+        ("tensor_inputs.css", "", TEMPLATE_TENSOR_INPUTS_CSS),
+        ("provenance.css", "", PROVENANCE_CSS),
+        ("index.html", "IndexContext", TEMPLATE_INDEX),
+        (
+            "export_index.html",
+            "ExportIndexContext",
+            TEMPLATE_EXPORT_INDEX,
+        ),
+        (
+            "failures_and_restarts.html",
+            "RestartsAndFailuresContext",
+            TEMPLATE_FAILURES_AND_RESTARTS,
+        ),
+        (
+            "dynamo_guards.html",
+            "DynamoGuardsContext",
+            TEMPLATE_DYNAMO_GUARDS,
+        ),
+        ("guard_diff.html", "GuardDiffContext", TEMPLATE_GUARD_DIFF),
+        (
+            "dynamic_shapes.html",
+            "DynamicShapesContext",
+            TEMPLATE_DYNAMIC_SHAPES,
+        ),
+        (
+            "cache_report.html",
+            "CacheReportContext",
+            TEMPLATE_CACHE_REPORT,
+        ),
+        ("kernels.html", "KernelsContext", TEMPLATE_KERNELS),
+        ("autotuning.html", "AutotuningContext", TEMPLATE_AUTOTUNING),
+        (
+            "runtime_metrics_table.html",
+            "CompilationMetricsRuntimeMetadata",
+            TEMPLATE_RUNTIME_METRICS_TABLE,
+        ),
+        (
+            "compilation_metrics_runtime.html",
+            "CompilationMetricsRuntimeContext",
+            TEMPLATE_COMPILATION_METRICS_RUNTIME,
+        ),
+        (
+            "feature_usage.html",
+            "FeatureUsageContext",
+            TEMPLATE_FEATURE_USAGE,
+        ),
+        (
+            "compilation_metrics.html",
+            "CompilationMetricsContext",
+            TEMPLATE_COMPILATION_METRICS,
+        ),
+        (
+            "tensor_inputs.html",
+            "TensorInputsContext",
+            TEMPLATE_TENSOR_INPUTS,
+        ),
+        ("json_table.html", "JsonTableContext", TEMPLATE_JSON_TABLE),
+        ("markdown.html", "MarkdownContext", TEMPLATE_MARKDOWN),
+        (
+            "bwd_compilation_metrics.html",
+            "BwdCompilationMetricsContext",
+            TEMPLATE_BWD_COMPILATION_METRICS,
+        ),
+        (
+            "aot_autograd_backward_compilation_metrics.html",
+            "AOTAutogradBackwardCompilationMetricsContext",
+            TEMPLATE_AOT_AUTOGRAD_BACKWARD_COMPILATION_METRICS,
+        ),
+        (
+            "symbolic_guard_information.html",
+            "SymbolicGuardContext",
+            TEMPLATE_SYMBOLIC_GUARD_INFO,
+        ),
+        (
+            "provenance_tracking.html",
+            "ProvenanceContext",
+            TEMPLATE_PROVENANCE_TRACKING,
+        ),
+        (
+            "multi_rank_index.html",
+            "MultiRankContext",
+            TEMPLATE_MULTI_RANK_INDEX,
+        ),
+    ]
+}
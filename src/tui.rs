@@ -0,0 +1,596 @@
+//! `tlparse tui`: browse the report of a log in the terminal, for when there's no browser to open
+//! it in (e.g. over ssh).
+//!
+//! It browses the records of a parse rather than the rendered pages: compile ids with their
+//! status, files, failures and the stack trie, from the same lazily rendered report as
+//! `tlparse serve`.  The pager shows text outputs as they are, and for pages shows what they
+//! were rendered from: the payload of the envelope, or its metadata if it has no payload.
+
+use anyhow::Context;
+use fxhash::FxHashMap;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::path::Path;
+
+use crate::parsers::{highlight_terminal_lines, CodeLanguage};
+use crate::serve::LazyReport;
+use crate::types::{CompileStatus, Envelope, OutputFile};
+
+struct CompileIdEntry {
+    id: String,
+    status: CompileStatus,
+    // First failure or restart, as `type: reason` on one line
+    failure: Option<String>,
+    artifacts: Vec<OutputFile>,
+}
+
+struct TrieRow {
+    depth: usize,
+    frame: String,
+    // Indices into App::compile_ids, None for a stack without a compile id
+    compile_ids: Vec<Option<usize>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum View {
+    StackTrie,
+    CompileIds,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    List,
+    Artifacts,
+}
+
+struct Pager {
+    title: String,
+    lines: Vec<Line<'static>>,
+    scroll: usize,
+}
+
+pub struct App<'c> {
+    report: LazyReport<'c>,
+    title: String,
+    compile_ids: Vec<CompileIdEntry>,
+    trie: Vec<TrieRow>,
+    view: View,
+    focus: Focus,
+    trie_state: ListState,
+    compile_id_state: ListState,
+    artifact_state: ListState,
+    pager: Option<Pager>,
+    // Rows the pager showed last time it was drawn, for paging
+    pager_height: usize,
+    // Shown in the status line until the next key press
+    message: Option<String>,
+    quit: bool,
+}
+
+fn status_style(status: CompileStatus) -> Style {
+    // The colors of the stack trie in the HTML report
+    match status {
+        CompileStatus::Missing => Style::default().fg(Color::Magenta),
+        CompileStatus::Error => Style::default().fg(Color::Red),
+        CompileStatus::Empty => Style::default().fg(Color::Gray),
+        CompileStatus::Break => Style::default().fg(Color::LightGreen),
+        CompileStatus::Ok => Style::default().fg(Color::Green),
+    }
+}
+
+fn status_name(status: CompileStatus) -> &'static str {
+    match status {
+        CompileStatus::Missing => "metrics missing",
+        CompileStatus::Error => "error",
+        CompileStatus::Empty => "empty graph",
+        CompileStatus::Break => "restarted",
+        CompileStatus::Ok => "ok",
+    }
+}
+
+fn is_failure(status: CompileStatus) -> bool {
+    matches!(status, CompileStatus::Error | CompileStatus::Break)
+}
+
+fn move_selection(state: &mut ListState, len: usize, delta: isize) {
+    if len == 0 {
+        state.select(None);
+        return;
+    }
+    let current = state.selected().unwrap_or(0) as isize;
+    state.select(Some((current + delta).clamp(0, len as isize - 1) as usize));
+}
+
+impl<'c> App<'c> {
+    pub fn new(mut report: LazyReport<'c>, title: &str) -> anyhow::Result<Self> {
+        let summary = report.summary()?;
+        let mut failures: FxHashMap<&str, String> = FxHashMap::default();
+        for failure in &summary.failures {
+            failures
+                .entry(failure.compile_id.as_str())
+                .or_insert_with(|| {
+                    let reason = failure.reason.split_whitespace().collect::<Vec<_>>();
+                    format!("{}: {}", failure.failure_type, reason.join(" "))
+                });
+        }
+        let compile_ids: Vec<CompileIdEntry> = summary
+            .compile_ids
+            .iter()
+            .map(|id| CompileIdEntry {
+                id: id.clone(),
+                status: summary
+                    .statuses
+                    .get(id)
+                    .copied()
+                    .unwrap_or(CompileStatus::Missing),
+                failure: failures.get(id.as_str()).cloned(),
+                artifacts: summary.files.get(id).cloned().unwrap_or_default(),
+            })
+            .collect();
+
+        let index: FxHashMap<&str, usize> = compile_ids
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.id.as_str(), i))
+            .collect();
+        let trie = summary
+            .stack_trie
+            .text_lines()
+            .into_iter()
+            .map(|line| TrieRow {
+                depth: line.depth,
+                frame: line.frame,
+                compile_ids: line
+                    .compile_ids
+                    .iter()
+                    .map(|c| {
+                        c.as_ref()
+                            .and_then(|c| index.get(c.to_string().as_str()).copied())
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let mut app = App {
+            report,
+            title: title.to_string(),
+            compile_ids,
+            trie,
+            view: View::StackTrie,
+            focus: Focus::List,
+            trie_state: ListState::default(),
+            compile_id_state: ListState::default(),
+            artifact_state: ListState::default(),
+            pager: None,
+            pager_height: 0,
+            message: None,
+            quit: false,
+        };
+        if app.trie.is_empty() {
+            app.view = View::CompileIds;
+        }
+        move_selection(&mut app.trie_state, app.trie.len(), 0);
+        move_selection(&mut app.compile_id_state, app.compile_ids.len(), 0);
+        app.reset_artifacts();
+        Ok(app)
+    }
+
+    // Compile id the artifact pane shows: the selected one, or the first one on the selected
+    // line of the stack trie
+    fn selected_compile_id(&self) -> Option<usize> {
+        match self.view {
+            View::CompileIds => self.compile_id_state.selected(),
+            View::StackTrie => self
+                .trie_state
+                .selected()
+                .and_then(|row| self.trie[row].compile_ids.iter().flatten().next().copied()),
+        }
+    }
+
+    fn selected_artifact(&self) -> Option<&OutputFile> {
+        let entry = &self.compile_ids[self.selected_compile_id()?];
+        entry.artifacts.get(self.artifact_state.selected()?)
+    }
+
+    fn reset_artifacts(&mut self) {
+        let len = self
+            .selected_compile_id()
+            .map_or(0, |i| self.compile_ids[i].artifacts.len());
+        self.artifact_state.select(None);
+        move_selection(&mut self.artifact_state, len, 0);
+    }
+
+    fn list_len(&self) -> usize {
+        match self.view {
+            View::StackTrie => self.trie.len(),
+            View::CompileIds => self.compile_ids.len(),
+        }
+    }
+
+    fn move_list(&mut self, delta: isize) {
+        let len = self.list_len();
+        match self.focus {
+            Focus::List => {
+                let state = match self.view {
+                    View::StackTrie => &mut self.trie_state,
+                    View::CompileIds => &mut self.compile_id_state,
+                };
+                move_selection(state, len, delta);
+                self.reset_artifacts();
+            }
+            Focus::Artifacts => {
+                let len = self
+                    .selected_compile_id()
+                    .map_or(0, |i| self.compile_ids[i].artifacts.len());
+                move_selection(&mut self.artifact_state, len, delta);
+            }
+        }
+    }
+
+    // Select the next (or previous) row with a failed or restarted compile id, wrapping around
+    fn jump_to_failure(&mut self, forward: bool) {
+        let len = self.list_len();
+        let failed = |app: &App<'_>, row: usize| match app.view {
+            View::StackTrie => app.trie[row]
+                .compile_ids
+                .iter()
+                .flatten()
+                .any(|&i| is_failure(app.compile_ids[i].status)),
+            View::CompileIds => is_failure(app.compile_ids[row].status),
+        };
+        let current = match self.view {
+            View::StackTrie => self.trie_state.selected(),
+            View::CompileIds => self.compile_id_state.selected(),
+        }
+        .unwrap_or(0);
+        let found = (1..=len)
+            .map(|step| {
+                if forward {
+                    (current + step) % len
+                } else {
+                    (current + len - step % len) % len
+                }
+            })
+            .find(|&row| failed(self, row));
+        match found {
+            Some(row) => {
+                match self.view {
+                    View::StackTrie => self.trie_state.select(Some(row)),
+                    View::CompileIds => self.compile_id_state.select(Some(row)),
+                }
+                self.focus = Focus::List;
+                self.reset_artifacts();
+            }
+            None => self.message = Some("No failures or restarts".to_string()),
+        }
+    }
+
+    fn switch_view(&mut self, view: View) {
+        // Keep the compile id selected in the stack trie
+        if self.view == View::StackTrie && view == View::CompileIds {
+            if let Some(i) = self.selected_compile_id() {
+                self.compile_id_state.select(Some(i));
+            }
+        }
+        self.view = view;
+        self.focus = Focus::List;
+        self.reset_artifacts();
+    }
+
+    fn open_artifact(&mut self) {
+        let Some(artifact) = self.selected_artifact().cloned() else {
+            return;
+        };
+        match self.render_artifact(&artifact) {
+            Ok(lines) => {
+                self.pager = Some(Pager {
+                    title: artifact.url,
+                    lines,
+                    scroll: 0,
+                })
+            }
+            Err(err) => self.message = Some(format!("Failed to render {}: {err:#}", artifact.url)),
+        }
+    }
+
+    fn render_artifact(&mut self, artifact: &OutputFile) -> anyhow::Result<Vec<Line<'static>>> {
+        let path = Path::new(&artifact.url);
+        let (text, language) = match artifact.lineno {
+            // Pages are shown as what they were rendered from
+            Some(lineno) if artifact.url.ends_with(".html") => {
+                let (metadata, payload) = self.report.envelope(lineno)?;
+                if payload.is_empty() {
+                    (serde_json::to_string_pretty(&metadata)?, None)
+                } else {
+                    // inductor_output_code is highlighted by its parser, so there's no view
+                    // of it to take the language from
+                    let language =
+                        serde_json::from_value::<Envelope>(metadata)
+                            .ok()
+                            .and_then(|e| match e.inductor_output_code {
+                                Some(_) => Some(CodeLanguage::Python),
+                                None => CodeLanguage::of_envelope(&e),
+                            });
+                    (payload, language)
+                }
+            }
+            _ => {
+                let content = self
+                    .report
+                    .get(path)?
+                    .with_context(|| format!("{} is not part of this report", artifact.url))?;
+                (content.to_string(), self.report.language(path))
+            }
+        };
+        let styled: Vec<Vec<Span<'static>>> = match language {
+            Some(language) if text.len() <= crate::MAX_HIGHLIGHTED_BYTES => {
+                highlight_terminal_lines(&text, language)?
+                    .into_iter()
+                    .map(|regions| {
+                        regions
+                            .into_iter()
+                            .map(|(style, text)| {
+                                let fg = style.foreground;
+                                Span::styled(
+                                    text,
+                                    Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b)),
+                                )
+                            })
+                            .collect()
+                    })
+                    .collect()
+            }
+            _ => text
+                .lines()
+                .map(|line| vec![Span::raw(line.to_string())])
+                .collect(),
+        };
+        // Numbered like the L<n> anchors of the HTML views
+        let width = styled.len().to_string().len();
+        Ok(styled
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut spans)| {
+                spans.insert(
+                    0,
+                    Span::styled(
+                        format!("{:>width$} ", i + 1),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                );
+                Line::from(spans)
+            })
+            .collect())
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        self.message = None;
+        if let Some(pager) = &mut self.pager {
+            let page = self.pager_height.max(1);
+            let last = pager.lines.len().saturating_sub(1);
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc | KeyCode::Left => self.pager = None,
+                KeyCode::Down | KeyCode::Char('j') => pager.scroll += 1,
+                KeyCode::Up | KeyCode::Char('k') => pager.scroll = pager.scroll.saturating_sub(1),
+                KeyCode::PageDown | KeyCode::Char(' ') => pager.scroll += page,
+                KeyCode::PageUp => pager.scroll = pager.scroll.saturating_sub(page),
+                KeyCode::Home | KeyCode::Char('g') => pager.scroll = 0,
+                KeyCode::End | KeyCode::Char('G') => pager.scroll = last,
+                _ => {}
+            }
+            if let Some(pager) = &mut self.pager {
+                pager.scroll = pager.scroll.min(last);
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Char('1') => self.switch_view(View::StackTrie),
+            KeyCode::Char('2') => self.switch_view(View::CompileIds),
+            KeyCode::Char('f') => self.jump_to_failure(true),
+            KeyCode::Char('F') => self.jump_to_failure(false),
+            KeyCode::Down | KeyCode::Char('j') => self.move_list(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_list(-1),
+            KeyCode::PageDown => self.move_list(10),
+            KeyCode::PageUp => self.move_list(-10),
+            KeyCode::Home | KeyCode::Char('g') => self.move_list(isize::MIN / 2),
+            KeyCode::End | KeyCode::Char('G') => self.move_list(isize::MAX / 2),
+            KeyCode::Tab => {
+                self.focus = match self.focus {
+                    Focus::List => Focus::Artifacts,
+                    Focus::Artifacts => Focus::List,
+                }
+            }
+            KeyCode::Enter | KeyCode::Right => match self.focus {
+                Focus::List => self.focus = Focus::Artifacts,
+                Focus::Artifacts => self.open_artifact(),
+            },
+            KeyCode::Esc | KeyCode::Left => self.focus = Focus::List,
+            _ => {}
+        }
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let tab = |label: &'static str, view: View| {
+            if self.view == view {
+                Span::styled(label, Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                Span::raw(label)
+            }
+        };
+        frame.render_widget(
+            Line::from(vec![
+                Span::styled(
+                    format!(" tlparse: {} ", self.title),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                tab(" 1 Stack trie ", View::StackTrie),
+                Span::raw(" "),
+                tab(" 2 Compile ids ", View::CompileIds),
+            ]),
+            header,
+        );
+
+        let help = match (&self.message, &self.pager) {
+            (Some(message), _) => message.clone(),
+            (None, Some(_)) => "j/k scroll  space/PgUp page  g/G top/bottom  q back".to_string(),
+            (None, None) => {
+                "j/k move  enter open  tab switch pane  f/F next/prev failure  1/2 view  q quit"
+                    .to_string()
+            }
+        };
+        frame.render_widget(
+            Line::styled(help, Style::default().fg(Color::DarkGray)),
+            footer,
+        );
+
+        if let Some(pager) = &self.pager {
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title(pager.title.clone());
+            self.pager_height = block.inner(body).height as usize;
+            let lines: Vec<Line> = pager
+                .lines
+                .iter()
+                .skip(pager.scroll)
+                .take(self.pager_height)
+                .cloned()
+                .collect();
+            frame.render_widget(Paragraph::new(lines).block(block), body);
+            return;
+        }
+
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(body);
+        let focused = |focus: Focus| {
+            if self.focus == focus {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default()
+            }
+        };
+        let highlight = Style::default().add_modifier(Modifier::REVERSED);
+
+        let (items, title): (Vec<ListItem>, &str) = match self.view {
+            View::StackTrie => (
+                self.trie
+                    .iter()
+                    .map(|row| {
+                        let mut spans = vec![Span::raw("  ".repeat(row.depth))];
+                        for c in &row.compile_ids {
+                            spans.push(match c {
+                                Some(i) => Span::styled(
+                                    self.compile_ids[*i].id.clone(),
+                                    status_style(self.compile_ids[*i].status),
+                                ),
+                                None => Span::raw("(unknown)"),
+                            });
+                            spans.push(Span::raw(" "));
+                        }
+                        spans.push(Span::raw(row.frame.clone()));
+                        ListItem::new(Line::from(spans))
+                    })
+                    .collect(),
+                "Stack trie",
+            ),
+            View::CompileIds => (
+                self.compile_ids
+                    .iter()
+                    .map(|c| {
+                        ListItem::new(Line::from(vec![
+                            Span::styled(format!("{:<12}", c.id), status_style(c.status)),
+                            Span::raw(format!(" {:<16}", status_name(c.status))),
+                            Span::raw(c.failure.clone().unwrap_or_default()),
+                        ]))
+                    })
+                    .collect(),
+                "Compile ids",
+            ),
+        };
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(focused(Focus::List))
+                    .title(title),
+            )
+            .highlight_style(highlight);
+        let state = match self.view {
+            View::StackTrie => &mut self.trie_state,
+            View::CompileIds => &mut self.compile_id_state,
+        };
+        frame.render_stateful_widget(list, list_area, state);
+
+        let entry = self.selected_compile_id().map(|i| &self.compile_ids[i]);
+        let [info_area, artifact_area] =
+            Layout::vertical([Constraint::Length(5), Constraint::Min(0)]).areas(detail_area);
+        let info = match entry {
+            Some(c) => vec![
+                Line::from(vec![
+                    Span::styled(c.id.clone(), status_style(c.status)),
+                    Span::raw(format!(" {}", status_name(c.status))),
+                ]),
+                Line::raw(c.failure.clone().unwrap_or_default()),
+            ],
+            None => vec![Line::raw("No compile id")],
+        };
+        frame.render_widget(
+            Paragraph::new(info)
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).title("Compile id")),
+            info_area,
+        );
+        let artifacts: Vec<ListItem> = entry
+            .map(|c| {
+                c.artifacts
+                    .iter()
+                    .map(|a| ListItem::new(a.name.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        frame.render_stateful_widget(
+            List::new(artifacts)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(focused(Focus::Artifacts))
+                        .title("Artifacts"),
+                )
+                .highlight_style(highlight),
+            artifact_area,
+            &mut self.artifact_state,
+        );
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Take over the terminal and run `app` until it quits
+pub fn run(mut app: App<'_>) -> anyhow::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = app.event_loop(&mut terminal);
+    ratatui::restore();
+    result
+}
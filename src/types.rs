@@ -20,6 +20,11 @@ pub type SymbolicShapeSpecializationIndex =
     FxHashMap<Option<CompileId>, Vec<SymbolicShapeSpecializationMetadata>>;
 pub type GuardAddedFastIndex = FxHashMap<Option<CompileId>, Vec<GuardAddedFastMetadata>>;
 pub type SymExprInfoIndex = FxHashMap<u64, SymExprInfoMetadata>;
+pub type GuardSetIndex = FxIndexMap<Option<CompileId>, GuardSets>;
+pub type RecompileReasonsIndex = FxHashMap<Option<CompileId>, Vec<String>>;
+pub type TensorDescriptionIndex = FxHashMap<Option<CompileId>, TensorDescriptions>;
+pub type CompilationMetricsPageIndex = FxHashMap<Option<CompileId>, CompilationMetricsPage>; // NB: attempt is always None here
+pub type RuntimeMetricsIndex = FxHashMap<Option<CompileId>, Vec<CompilationMetricsRuntimeMetadata>>; // NB: attempt is always None here
 
 pub type FxIndexMap<K, V> = IndexMap<K, V, BuildHasherDefault<FxHasher>>;
 
@@ -115,7 +120,55 @@ pub fn extract_eval_with_key_id(filename: &str) -> Option<u64> {
 pub static INTERN_TABLE: Lazy<Mutex<FxHashMap<u32, String>>> =
     Lazy::new(|| Mutex::new(FxHashMap::default()));
 
-#[derive(Default)]
+/// How a compile went, as colored in the stack trie
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompileStatus {
+    Missing,
+    Error,
+    Empty,
+    Break,
+    Ok,
+}
+
+impl CompileStatus {
+    pub fn of(metrics: Option<&Vec<CompilationMetricsMetadata>>) -> Self {
+        metrics.map_or(CompileStatus::Missing, |m| {
+            if m.iter().any(|n| n.fail_type.is_some()) {
+                CompileStatus::Error
+            } else if m.iter().any(|n| n.graph_op_count.unwrap_or(0) == 0) {
+                CompileStatus::Empty
+            } else if m
+                .iter()
+                .any(|n| !n.restart_reasons.as_ref().map_or(false, |o| o.is_empty()))
+            {
+                CompileStatus::Break
+            } else {
+                CompileStatus::Ok
+            }
+        })
+    }
+
+    pub fn css_class(self) -> &'static str {
+        match self {
+            CompileStatus::Missing => "status-missing",
+            CompileStatus::Error => "status-error",
+            CompileStatus::Empty => "status-empty",
+            CompileStatus::Break => "status-break",
+            CompileStatus::Ok => "status-ok",
+        }
+    }
+}
+
+/// A line of the stack trie as plain text: a frame, indented, and the compile ids whose stacks
+/// end there
+#[derive(Debug)]
+pub struct StackTrieLine {
+    pub depth: usize,
+    pub frame: String,
+    pub compile_ids: Vec<Option<CompileId>>,
+}
+
+#[derive(Default, Debug, Clone)]
 pub struct StackTrieNode {
     terminal: Vec<Option<CompileId>>,
     // Ordered map so that when we print we roughly print in chronological order
@@ -170,19 +223,7 @@ impl StackTrieNode {
             for t in &node.terminal {
                 if let Some(c) = t {
                     let ok_class = mb_metrics_index.map_or("status-missing", |metrics_index| {
-                        metrics_index.get(t).map_or("status-missing", |m| {
-                            if m.iter().any(|n| n.fail_type.is_some()) {
-                                "status-error"
-                            } else if m.iter().any(|n| n.graph_op_count.unwrap_or(0) == 0) {
-                                "status-empty"
-                            } else if m.iter().any(|n| {
-                                !n.restart_reasons.as_ref().map_or(false, |o| o.is_empty())
-                            }) {
-                                "status-break"
-                            } else {
-                                "status-ok"
-                            }
-                        })
+                        CompileStatus::of(metrics_index.get(t)).css_class()
                     });
                     write!(
                         star,
@@ -213,6 +254,29 @@ impl StackTrieNode {
         }
         Ok(())
     }
+
+    /// The trie as plain text lines, indented the same way as `fmt`
+    pub fn text_lines(&self) -> Vec<StackTrieLine> {
+        let mut lines = Vec::new();
+        self.text_lines_inner(0, &mut lines);
+        lines
+    }
+
+    fn text_lines_inner(&self, depth: usize, lines: &mut Vec<StackTrieLine>) {
+        let child_depth = if self.children.len() > 1 {
+            depth + 1
+        } else {
+            depth
+        };
+        for (frame, node) in self.children.iter() {
+            lines.push(StackTrieLine {
+                depth,
+                frame: frame.plain_text(),
+                compile_ids: node.terminal.clone(),
+            });
+            node.text_lines_inner(child_depth, lines);
+        }
+    }
 }
 
 #[derive(Eq, PartialEq, Hash, Deserialize, Serialize, Debug, Clone)]
@@ -261,20 +325,37 @@ impl CompileId {
     }
 }
 
-#[derive(Default, Debug)]
+// Written as parse_stats.json
+#[derive(Default, Debug, Serialize)]
 pub struct Stats {
     pub ok: u64,
     pub other_rank: u64,
     pub fail_glog: u64,
     pub fail_json: u64,
     pub fail_payload_md5: u64,
+    // The dynamo_guards/payload_decode entry of parser_errors, kept apart from fail_parser
     pub fail_dynamo_guards_json: u64,
     pub fail_parser: u64,
+    // Parser failures by (parser name, ParserError kind)
+    #[serde(serialize_with = "serialize_parser_errors")]
+    pub parser_errors: FxIndexMap<(String, String), u64>,
     pub fail_key_conflict: u64,
     pub fail_json_serialization: u64,
     pub unknown: u64,
 }
 
+// JSON objects can't have tuple keys, so these are written as [parser, kind, count] triples
+fn serialize_parser_errors<S: serde::Serializer>(
+    parser_errors: &FxIndexMap<(String, String), u64>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(
+        parser_errors
+            .iter()
+            .map(|((parser, kind), count)| (parser, kind, count)),
+    )
+}
+
 impl std::fmt::Display for Stats {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut fields = Vec::new();
@@ -303,6 +384,14 @@ impl std::fmt::Display for Stats {
         if self.fail_parser > 0 {
             fields.push(format!("fail_parser: {}", self.fail_parser));
         }
+        if !self.parser_errors.is_empty() {
+            let by_kind: Vec<String> = self
+                .parser_errors
+                .iter()
+                .map(|((parser, kind), count)| format!("{parser}/{kind}: {count}"))
+                .collect();
+            fields.push(format!("parser_errors: {{{}}}", by_kind.join(", ")));
+        }
         if self.fail_key_conflict > 0 {
             fields.push(format!("fail_key_conflict: {}", self.fail_key_conflict));
         }
@@ -355,6 +444,22 @@ pub fn unintern_str(interned_str: u32) -> String {
     return filename.to_string();
 }
 
+impl FrameSummary {
+    /// `file:line in name`, without the markup of the Display impl
+    pub fn plain_text(&self) -> String {
+        let filename = match &self.uninterned_filename {
+            Some(f) => f.clone(),
+            None => unintern_str(self.filename),
+        };
+        format!(
+            "{}:{} in {}",
+            simplify_filename(&filename),
+            self.line,
+            self.name
+        )
+    }
+}
+
 impl fmt::Display for FrameSummary {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let intern_table = INTERN_TABLE.lock().unwrap();
@@ -418,6 +523,9 @@ pub struct EmptyMetadata {}
 #[derive(Debug, Deserialize)]
 pub struct GraphDumpMetadata {
     pub name: String,
+    // Language of the dump, e.g. "cpp" for AOT inductor code; FX graphs leave it out
+    #[serde(rename = "type")]
+    pub dump_type: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -473,6 +581,25 @@ pub struct CompilationMetricsMetadata {
     pub compliant_custom_ops: Option<Vec<String>>,
     pub restart_reasons: Option<Vec<String>>,
     pub dynamo_time_before_restart_s: Option<f64>,
+    pub feature_usage: Option<FxHashMap<String, bool>>,
+    pub compile_time_autotune_time_us: Option<u64>,
+    // A JSON list of [kernel name, compile time in us] pairs
+    pub triton_kernel_compile_times_us: Option<String>,
+}
+
+/// Metrics logged when a compiled graph is first run, e.g. cudagraphify time.  Most of the
+/// compile time fields are null here, so only the runtime ones are kept.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CompilationMetricsRuntimeMetadata {
+    pub is_forward: Option<bool>,
+    pub start_time_us: Option<u64>,
+    pub end_time_us: Option<u64>,
+    pub duration_us: Option<u64>,
+    pub runtime_cudagraphify_time_us: Option<u64>,
+    pub runtime_triton_autotune_time_us: Option<u64>,
+    pub cuda_version: Option<String>,
+    pub triton_version: Option<String>,
+    pub python_version: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -553,6 +680,16 @@ pub struct UnbackedSymbolMetadata {
     pub vr: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateSymbolMetadata {
+    pub symbol: Option<String>,
+    pub val: Option<String>,
+    pub vr: Option<String>,
+    pub source: Option<String>,
+    pub user_stack: Option<StackSummary>,
+    pub stack: Option<StackSummary>,
+}
+
 #[derive(Default, Debug, Deserialize, Serialize)]
 pub struct SymExprInfoMetadata {
     pub method: Option<String>,
@@ -588,25 +725,191 @@ pub struct AOTAutogradBackwardCompilationMetricsContext<'e> {
 
 #[derive(Clone, Debug, Serialize)]
 pub struct OutputFile {
+    /// URL relative to the output root, or the link target for a link
     pub url: String,
+    /// Path shown in the index, the same as url unless the file is a link
     pub name: String,
+    /// Position of the file in the order tlparse wrote the run's outputs
     pub number: i32,
+    /// ✅, ❌ or ❓ for cache hits, misses and bypasses, empty otherwise
     pub suffix: String,
-    /// URL to a human-readable HTML version of inductor_provenance_tracking_kernel_stack_traces.json
+    /// URL to a human-readable HTML version: rendered
+    /// inductor_provenance_tracking_kernel_stack_traces.json, or highlighted code
     pub readable_url: Option<String>,
-}
-
+    /// Index group from tlparse.toml, and whether this is the first file listed in it
+    pub group: Option<String>,
+    pub group_start: bool,
+    /// md5 of the file's content with --content-addressed, None otherwise and for links
+    pub hash: Option<String>,
+    /// Line of the envelope it was rendered from
+    #[serde(skip)]
+    pub lineno: Option<usize>,
+}
+
+/// Context of `compilation_metrics.html`, one page per compile id.  Like the other contexts
+/// of overridable templates, its fields are part of the `--template-dir` contract.
 #[derive(Debug, Serialize)]
 pub struct CompilationMetricsContext<'e> {
+    /// The `compilation_metrics` envelope as logged; fields PyTorch didn't log are None
     pub m: &'e CompilationMetricsMetadata,
+    /// `main.css`, or its `--template-dir` override
     pub css: &'static str,
+    /// e.g. `[0/1]`
+    pub compile_id: &'e str,
+    /// Stack the frame was compiled from, as HTML; empty if no `dynamo_start` was logged
+    pub stack_html: &'e str,
+    /// Shape specializations made during the compile; empty if there were none
+    pub symbolic_shape_specializations: &'e [SymbolicShapeSpecializationContext],
+    /// Guards added on the fast path during the compile; empty if there were none
+    pub guards_added_fast: &'e [GuardAddedFastContext],
+    /// Files output for this compile id before its metrics, in log order, with URLs and names
+    /// relative to the compile id's directory
+    pub output_files: &'e [OutputFile],
+    /// Directory of the compile id's files, e.g. `-_0_1_0`
+    pub compile_id_dir: &'e PathBuf,
+    /// File name of the compile's tensor inputs page in `compile_id_dir`; None if it has none
+    pub tensor_inputs_url: Option<&'e str>,
+    /// `compilation_metrics_runtime` envelopes of the compile; empty until its graphs run
+    pub runtime_metrics: &'e [CompilationMetricsRuntimeMetadata],
+    /// The compiled function's definition as a one frame stack, as HTML; empty if the metrics
+    /// don't name it
+    pub mini_stack_html: &'e str,
+    /// Script that keeps query parameters on links; render with `format_unescaped`
+    pub qps: &'static str,
+}
+
+/// What the compilation metrics page of a compile shows.  Pages are kept until the end of the
+/// parse, since the runtime metrics shown on them are logged when the graphs first run.
+#[derive(Debug)]
+pub struct CompilationMetricsPage {
+    pub m: CompilationMetricsMetadata,
     pub compile_id: String,
     pub stack_html: String,
+    pub mini_stack_html: String,
     pub symbolic_shape_specializations: Vec<SymbolicShapeSpecializationContext>,
     pub guards_added_fast: Vec<GuardAddedFastContext>,
-    pub output_files: &'e Vec<OutputFile>,
-    pub compile_id_dir: &'e PathBuf,
-    pub mini_stack_html: String,
+    pub output_files: Vec<OutputFile>,
+    pub compile_id_dir: PathBuf,
+    pub tensor_inputs_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompilationMetricsRuntimeContext<'e> {
+    pub m: &'e CompilationMetricsRuntimeMetadata,
+    pub css: &'static str,
+    pub compile_id: String,
+    pub qps: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeatureUsageRow {
+    pub feature: String,
+    pub enabled_in: Vec<String>,
+    pub disabled_in: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeatureUsageContext {
+    pub css: &'static str,
+    pub features: Vec<FeatureUsageRow>,
+    pub qps: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TensorInputContext {
+    pub id: u64,
+    pub sources: Vec<String>,
+    pub dtype: String,
+    pub device: String,
+    pub size_html: String,
+    pub stride_html: String,
+    pub storage: String,
+    pub requires_grad: bool,
+    pub is_leaf: bool,
+    pub is_parameter: bool,
+    pub view: String,
+    pub is_dynamic: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TensorInputsContext {
+    pub css: &'static str,
+    pub compile_id: String,
+    pub inputs: Vec<TensorInputContext>,
+    pub qps: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonTableContext {
+    pub css: &'static str,
+    pub title: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub qps: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MarkdownContext {
+    pub css: &'static str,
+    pub title: String,
+    pub body: String,
+    pub qps: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DynamicSymbolContext {
+    pub compile_id: String,
+    pub symbol: String,
+    pub unbacked: bool,
+    pub sources: Vec<String>,
+    // Value, range and user stack of the event that created the symbol
+    pub value: String,
+    pub value_range: String,
+    pub user_stack_html: String,
+    // Later events of the same compile that mention the symbol
+    pub uses: Vec<DynamicSymbolUseContext>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DynamicSymbolUseContext {
+    pub kind: &'static str,
+    pub expr: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DynamicSpecializationContext {
+    pub compile_id: String,
+    pub symbol: String,
+    pub sources: Vec<String>,
+    pub value: String,
+    pub reason: String,
+    pub user_stack_html: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DynamicGuardContext {
+    pub compile_id: String,
+    pub expr: String,
+    pub user_stack_html: String,
+}
+
+/// Symbolic shape events collected over the whole log.  Symbols are keyed by compile id and
+/// name, in creation order: every compile has its own ShapeEnv, which names its symbols s0, s1,
+/// ... afresh.
+#[derive(Debug, Default)]
+pub struct DynamicShapesIndex {
+    pub symbols: FxIndexMap<(String, String), DynamicSymbolContext>,
+    pub specializations: Vec<DynamicSpecializationContext>,
+    pub guards: Vec<DynamicGuardContext>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DynamicShapesContext {
+    pub css: &'static str,
+    pub symbols: Vec<DynamicSymbolContext>,
+    pub num_unbacked: usize,
+    pub specializations: Vec<DynamicSpecializationContext>,
+    pub guards: Vec<DynamicGuardContext>,
     pub qps: &'static str,
 }
 
@@ -696,12 +999,16 @@ pub enum Metadata<'e> {
     InductorOutputCode(&'e InductorOutputCodeMetadata),
     OptimizeDdpSplitChild(&'e OptimizeDdpSplitChildMetadata),
     CompilationMetrics(&'e CompilationMetricsMetadata),
+    CompilationMetricsRuntime(&'e CompilationMetricsRuntimeMetadata),
     AOTAutogradBackwardCompilationMetrics(&'e AOTAutogradBackwardCompilationMetricsMetadata),
     BwdCompilationMetrics(&'e BwdCompilationMetricsMetadata),
     Artifact(&'e ArtifactMetadata),
     DumpFile(&'e DumpFileMetadata),
     GuardAddedFast(&'e GuardAddedFastMetadata),
     SymbolicShapePropagateRealTensor(&'e SymbolicShapePropagateRealTensorMetadata),
+    // An envelope key tlparse doesn't know about (from Envelope._other), so that
+    // out-of-tree parsers can handle their own log types; see Envelope::other
+    Other(&'e str, &'e Value),
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -741,6 +1048,7 @@ pub struct Envelope {
     pub dynamo_cpp_guards_str: Option<EmptyMetadata>,
     pub inductor_output_code: Option<InductorOutputCodeMetadata>,
     pub compilation_metrics: Option<CompilationMetricsMetadata>,
+    pub compilation_metrics_runtime: Option<CompilationMetricsRuntimeMetadata>,
     pub bwd_compilation_metrics: Option<BwdCompilationMetricsMetadata>,
     pub aot_autograd_backward_compilation_metrics:
         Option<AOTAutogradBackwardCompilationMetricsMetadata>,
@@ -749,6 +1057,7 @@ pub struct Envelope {
     pub symbolic_shape_specialization: Option<SymbolicShapeSpecializationMetadata>,
     pub propagate_real_tensors_provenance: Option<SymbolicShapePropagateRealTensorMetadata>,
     pub guard_added: Option<SymbolicShapePropagateRealTensorMetadata>,
+    pub create_symbol: Option<CreateSymbolMetadata>,
     pub create_unbacked_symbol: Option<UnbackedSymbolMetadata>,
     pub expression_created: Option<SymExprInfoMetadata>,
     pub missing_fake_kernel: Option<FakeKernelMetadata>,
@@ -765,87 +1074,105 @@ pub struct Envelope {
     pub _other: FxHashMap<String, Value>,
 }
 
+impl Envelope {
+    /// Look up an envelope key that has no dedicated field, for use in
+    /// StructuredLogParser::get_metadata by custom parsers.
+    pub fn other(&self, key: &str) -> Option<Metadata<'_>> {
+        self._other
+            .get_key_value(key)
+            .map(|(k, v)| Metadata::Other(k.as_str(), v))
+    }
+}
+
 type MetaTensorId = u64;
 type MetaStorageId = u64;
 
+/// describe_tensor/describe_storage/describe_source entries logged while compiling a frame
+#[derive(Debug, Default)]
+pub struct TensorDescriptions {
+    pub tensors: Vec<TensorDesc>,
+    pub storages: Vec<StorageDesc>,
+    pub sources: Vec<SourceDesc>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct TensorDesc {
-    id: MetaTensorId,
-    describer_id: u64,
-    ndim: u64,
-    dtype: String,
-    device: String,
-    size: Vec<SymInt>,
-    dynamo_dynamic_indices: Option<Vec<u64>>,
+    pub id: MetaTensorId,
+    pub describer_id: u64,
+    pub ndim: u64,
+    pub dtype: String,
+    pub device: String,
+    pub size: Vec<SymInt>,
+    pub dynamo_dynamic_indices: Option<Vec<u64>>,
     // TODO: Make layout an enum
     #[serde(default = "default_layout")]
-    layout: String,
+    pub layout: String,
     #[serde(default)]
-    is_inference: bool,
+    pub is_inference: bool,
     #[serde(default)]
-    is_leaf: bool,
+    pub is_leaf: bool,
     #[serde(default)]
-    requires_grad: bool,
+    pub requires_grad: bool,
     #[serde(default)]
-    is_sparse: bool,
+    pub is_sparse: bool,
     #[serde(default)]
-    is_mkldnn: bool,
+    pub is_mkldnn: bool,
     #[serde(default)]
-    is_functorch_wrapped: bool,
+    pub is_functorch_wrapped: bool,
     #[serde(default)]
-    is_batchedtensor: bool,
+    pub is_batchedtensor: bool,
     #[serde(default)]
-    is_legacy_batchedtensor: bool,
+    pub is_legacy_batchedtensor: bool,
     #[serde(default)]
-    is_gradtrackingtensor: bool,
+    pub is_gradtrackingtensor: bool,
     #[serde(default)]
-    is_view: bool,
+    pub is_view: bool,
     #[serde(default)]
-    is_nested: bool,
+    pub is_nested: bool,
     #[serde(default)]
-    is_traceable_wrapper_subclass: bool,
+    pub is_traceable_wrapper_subclass: bool,
     #[serde(default)]
-    is_functional: bool,
+    pub is_functional: bool,
     #[serde(default)]
-    is_conj: bool,
+    pub is_conj: bool,
     #[serde(default)]
-    is_neg: bool,
+    pub is_neg: bool,
     #[serde(default)]
-    is_parameter: bool,
-    stride: Option<Vec<SymInt>>,
+    pub is_parameter: bool,
+    pub stride: Option<Vec<SymInt>>,
     #[serde(default)]
-    storage_offset: SymInt,
-    storage: Option<MetaStorageId>,
-    sparse_dim: Option<u64>,
-    dense_dim: Option<u64>,
-    is_coalesced: Option<bool>,
-    crow_indices: Option<MetaTensorId>,
-    col_indices: Option<MetaTensorId>,
-    ccol_indices: Option<MetaTensorId>,
-    row_indices: Option<MetaTensorId>,
-    values: Option<MetaTensorId>,
-    unwrapped: Option<MetaTensorId>,
-    bdim: Option<u64>,
-    base: Option<MetaTensorId>,
-    attrs: Option<FxHashMap<String, MetaTensorId>>,
-    creation_meta: Option<String>,
-    grad: Option<MetaTensorId>,
+    pub storage_offset: SymInt,
+    pub storage: Option<MetaStorageId>,
+    pub sparse_dim: Option<u64>,
+    pub dense_dim: Option<u64>,
+    pub is_coalesced: Option<bool>,
+    pub crow_indices: Option<MetaTensorId>,
+    pub col_indices: Option<MetaTensorId>,
+    pub ccol_indices: Option<MetaTensorId>,
+    pub row_indices: Option<MetaTensorId>,
+    pub values: Option<MetaTensorId>,
+    pub unwrapped: Option<MetaTensorId>,
+    pub bdim: Option<u64>,
+    pub base: Option<MetaTensorId>,
+    pub attrs: Option<FxHashMap<String, MetaTensorId>>,
+    pub creation_meta: Option<String>,
+    pub grad: Option<MetaTensorId>,
     #[serde(flatten)]
     pub _other: FxHashMap<String, Value>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct StorageDesc {
-    id: MetaStorageId,
-    describer_id: u64,
-    size: u64,
+    pub id: MetaStorageId,
+    pub describer_id: u64,
+    pub size: u64,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SourceDesc {
-    describer_id: u64,
-    id: MetaTensorId,
-    source: String,
+    pub describer_id: u64,
+    pub id: MetaTensorId,
+    pub source: String,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -855,26 +1182,384 @@ pub struct DynamoGuard {
     pub user_stack: Option<StackSummary>,
 }
 
+/// A guard from `dynamo_guards` or `dynamo_cpp_guards_str`, reduced to its kind and code so
+/// that guard sets from different compiles of the same frame can be compared.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct NormalizedGuard {
+    pub kind: String,
+    pub code: String,
+}
+
+/// The guards of a compile, one set per format they were logged in.  The formats name and
+/// split guards differently, so sets are only compared with sets of the same format.
+#[derive(Debug, Default)]
+pub struct GuardSets {
+    pub dynamo_guards: Vec<NormalizedGuard>,
+    pub cpp_guards: Vec<NormalizedGuard>,
+}
+
+impl NormalizedGuard {
+    pub fn new(kind: &str, code: &str) -> Self {
+        NormalizedGuard {
+            kind: kind.to_string(),
+            code: code.split_whitespace().collect::<Vec<_>>().join(" "),
+        }
+    }
+
+    /// What the guard is about (e.g. `check_tensor(L['x']` or the left hand side of a
+    /// comparison).  A removed and an added guard with the same subject are reported as a
+    /// single changed guard.
+    pub fn subject(&self) -> String {
+        static CALL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^([\w.]+\([^,()]*)").unwrap());
+        let subject = if let Some(caps) = CALL_RE.captures(&self.code) {
+            caps.get(1).unwrap().as_str()
+        } else {
+            [
+                " == ", " != ", " <= ", " >= ", " < ", " > ", " is not ", " is ",
+            ]
+            .iter()
+            .filter_map(|op| self.code.find(op).map(|i| &self.code[..i]))
+            .min_by_key(|lhs| lhs.len())
+            .unwrap_or(&self.code)
+        };
+        format!("{}: {}", self.kind, subject)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChangedGuard {
+    pub kind: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Autotuning of one Triton kernel in a compile, from the `triton_kernel_info` artifact or,
+/// for compiles that didn't log one, the kernel compile times of the compilation metrics
+#[derive(Debug, Serialize)]
+pub struct AutotunedKernel {
+    pub name: String,
+    pub cache_state: String,
+    pub num_configs: String,
+    pub best_config: String,
+    pub time_ms: String,
+    #[serde(skip)]
+    pub time_us: u64,
+}
+
+/// `autotune_choices_stats` of a `<op>_template_autotuning` event.  Inductor only logs the
+/// fastest choice and the fastest Triton choice, not the timing of every choice.
+#[derive(Debug, Default, Deserialize)]
+pub struct AutotuneChoicesStats {
+    pub num_choices: Option<u64>,
+    pub num_triton_choices: Option<u64>,
+    pub best_kernel: Option<String>,
+    pub best_kernel_desc: Option<String>,
+    pub best_time: Option<f64>,
+    pub best_triton_kernel: Option<String>,
+    pub best_triton_kernel_desc: Option<String>,
+    pub best_triton_time: Option<f64>,
+}
+
+/// Autotuning of one op by `select_algorithm`, from its `<op>_template_autotuning` event.
+/// `best_triton` is empty when the winner is itself a Triton template.
+#[derive(Debug, Default, Serialize)]
+pub struct TemplateAutotuning {
+    pub op: String,
+    pub input_shapes: String,
+    pub input_dtypes: String,
+    pub num_choices: String,
+    pub num_triton_choices: String,
+    pub winner: String,
+    pub winner_desc: String,
+    pub winner_time_ms: String,
+    pub best_triton: String,
+    pub best_triton_desc: String,
+    pub best_triton_time_ms: String,
+    pub time_ms: String,
+    #[serde(skip)]
+    pub time_us: f64,
+}
+
+/// Autotuning of one compile id.  `autotune_time_s` and `runtime_autotune_time_s` come from
+/// the compilation metrics, benchmark runs from `InductorBenchmarker.benchmark_gpu` events.
+#[derive(Debug, Default, Serialize)]
+pub struct AutotuneCompile {
+    pub compile_id: String,
+    pub autotune_time_s: String,
+    pub runtime_autotune_time_s: String,
+    pub num_benchmarks: usize,
+    pub benchmark_time_ms: String,
+    #[serde(skip)]
+    pub benchmark_time_us: f64,
+    pub ops: Vec<TemplateAutotuning>,
+    pub kernels: Vec<AutotunedKernel>,
+}
+
+/// Total time of a kernel or op across the run, for ranking them in the autotuning report
+#[derive(Debug, Serialize)]
+pub struct AutotuneTimeSummary {
+    pub name: String,
+    pub count: usize,
+    pub time_s: String,
+    pub share: String,
+}
+
+/// Autotunings of the whole run, by compile id
+pub type AutotuningIndex = FxIndexMap<String, AutotuneCompile>;
+
+#[derive(Debug, Serialize)]
+pub struct AutotuningContext {
+    pub css: &'static str,
+    pub kernel_time_s: String,
+    pub num_benchmarks: usize,
+    pub benchmark_time_s: String,
+    pub autotune_time_s: String,
+    pub op_time_s: String,
+    pub ops: Vec<AutotuneTimeSummary>,
+    pub kernels: Vec<AutotuneTimeSummary>,
+    pub compiles: Vec<AutotuneCompile>,
+    pub qps: &'static str,
+}
+
+/// A kernel defined or called by an `inductor_output_code` payload.  `source` is None for
+/// extern kernels.
+#[derive(Debug)]
+pub struct ExtractedKernel {
+    pub name: String,
+    pub kind: String,
+    pub source_nodes: Vec<String>,
+    pub aten_nodes: Vec<String>,
+    pub source: Option<String>,
+}
+
+/// Per-kernel entry of the `triton_kernel_info` artifact
+#[derive(Debug, Deserialize)]
+pub struct TritonKernelInfo {
+    pub autotune_cache_state: Option<String>,
+    pub num_configs: Option<u64>,
+    pub compile_time_us: Option<u64>,
+    // As [name, value] pairs; only_config is logged instead when there was nothing to tune
+    pub best_config: Option<Vec<(String, Value)>>,
+    pub only_config: Option<Vec<(String, Value)>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KernelInventoryEntry {
+    pub name: String,
+    pub kind: String,
+    pub content_hash: String,
+    pub compile_ids: Vec<String>,
+    pub source_nodes: Vec<String>,
+    pub aten_nodes: Vec<String>,
+    pub autotune: Vec<String>,
+}
+
+/// Kernels of the whole run, keyed by a hash of their source (or by name for extern kernels)
+pub type KernelInventory = FxIndexMap<String, KernelInventoryEntry>;
+
+#[derive(Debug, Serialize)]
+pub struct FallbackCompile {
+    pub compile_id: String,
+    pub extern_kernels: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KernelsContext {
+    pub css: &'static str,
+    pub kernels: Vec<KernelInventoryEntry>,
+    pub num_definitions: usize,
+    pub fallback_compiles: Vec<FallbackCompile>,
+    pub qps: &'static str,
+}
+
+/// Payload of the `*_cache_{hit,miss,bypass,hash}` artifacts logged by the FX graph and
+/// AOTAutograd caches
+#[derive(Debug, Deserialize)]
+pub struct CacheArtifactMetadata {
+    pub key: Option<String>,
+    pub components: Option<Vec<String>>,
+    pub cache_state: Option<String>,
+    pub cache_bypass_reason: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct CacheEvent {
+    pub compile_id: Option<CompileId>,
+    pub layer: String,
+    pub state: String,
+    pub from_hash_artifact: bool,
+    pub key: Option<String>,
+    pub components: Vec<String>,
+    pub bypass_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheLayerSummary {
+    pub layer: String,
+    pub hits: usize,
+    pub misses: usize,
+    pub bypasses: usize,
+    pub hit_rate: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheBypassReason {
+    pub layer: String,
+    pub reason: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChangedCacheComponent {
+    pub name: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheMiss {
+    pub compile_id: String,
+    pub layer: String,
+    pub key: String,
+    pub prev_compile_id: Option<String>,
+    pub changed: Vec<ChangedCacheComponent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheReportContext {
+    pub css: &'static str,
+    pub layers: Vec<CacheLayerSummary>,
+    pub bypass_reasons: Vec<CacheBypassReason>,
+    pub misses: Vec<CacheMiss>,
+    pub qps: &'static str,
+}
+
+/// Guard differences between two consecutive compiles of the same frame
+#[derive(Debug, Serialize)]
+pub struct GuardDiff {
+    pub prev_compile_id: String,
+    pub compile_id: String,
+    pub added: Vec<NormalizedGuard>,
+    pub removed: Vec<NormalizedGuard>,
+    pub changed: Vec<ChangedGuard>,
+    pub num_unchanged: usize,
+    pub recompile_reasons: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GuardDiffContext {
+    pub css: &'static str,
+    pub diffs: Vec<GuardDiff>,
+    pub qps: &'static str,
+}
+
 #[derive(Debug, Serialize)]
 pub struct DynamoGuardsContext {
     pub guards: Vec<DynamoGuard>,
     pub qps: &'static str,
 }
 
+/// Context of `index.html`.  Fields of contexts rendered by overridable templates are what
+/// `--template-dir` templates can use, so they are only ever added to, not renamed or removed.
 #[derive(Debug, Serialize)]
 pub struct IndexContext {
+    /// `main.css`, or its `--template-dir` override
     pub css: &'static str,
+    /// Script that shows and hides the stack trie; render with `format_unescaped`
     pub javascript: &'static str,
-    pub directory: Vec<(String, Vec<OutputFile>)>,
+    /// Search box over `search_index.js`; render with `format_unescaped`
+    pub search_js: &'static str,
+    /// Filtering and sorting of the compile ids; render with `format_unescaped`
+    pub compile_ids_js: &'static str,
+    /// Every compile id in log order, as (compile id, its output files, what the index filters
+    /// and sorts it by).  Envelopes without a compile id are listed under `(unknown)`.
+    pub directory: Vec<(String, Vec<OutputFile>, CompileIdSummary)>,
+    /// Stack trie of the frames that were compiled, as HTML
     pub stack_trie_html: String,
+    /// Stack trie of the envelopes that logged a stack outside of any compile, as HTML; empty
+    /// unless `has_unknown_stack_trie`
     pub unknown_stack_trie_html: String,
     pub has_unknown_stack_trie: bool,
+    /// Failures and restarts listed in `failures_and_restarts.html`
     pub num_breaks: usize,
+    /// `--custom-header-html`, as given; empty if not passed
     pub custom_header_html: String,
+    /// Whether `chromium_events.json` has any events
     pub has_chromium_events: bool,
+    /// Script that keeps query parameters on links; render with `format_unescaped`
     pub qps: &'static str,
+    /// Whether `--inductor-provenance` was passed
     pub has_inductor_provenance: bool,
+    /// Compile id directories with a `provenance_tracking_<name>.html` page; empty unless
+    /// `has_inductor_provenance`
     pub directory_names: Vec<String>,
+    /// Recompiles shown in `guard_diff.html`; 0 if there were none or the page is skipped
+    pub num_guard_diffs: usize,
+    /// Whether each run-wide report was written; false if it had nothing to show or is skipped
+    pub has_dynamic_shapes: bool,
+    pub has_cache_report: bool,
+    pub has_kernels: bool,
+    pub has_autotuning: bool,
+    pub has_feature_usage: bool,
+    /// Envelope keys no parser knows, with how many times each was logged
+    pub unknown_fields: Vec<(String, u64)>,
+    /// Run-wide outputs from StructuredLogParser::finish, as (name, url)
+    pub parser_outputs: Vec<(String, String)>,
+    /// Parser failures as (parser, error kind, count)
+    pub parser_errors: Vec<(String, String, u64)>,
+}
+
+/// Per compile id metadata for filtering and sorting the index
+#[derive(Debug, Serialize)]
+pub struct CompileIdSummary {
+    // status-* class, as in the stack trie
+    pub status: &'static str,
+    pub start_time: Option<f64>,
+    pub compile_time_s: Option<f64>,
+    pub graph_op_count: Option<u64>,
+    // hit, miss or bypass; a miss on any cache outranks a hit, which outranks a bypass
+    pub cache_status: Option<&'static str>,
+    pub rank: Option<u32>,
+    // The above as a line of text, e.g. "1.23s, 45 ops, cache miss"
+    pub description: String,
+}
+
+impl CompileIdSummary {
+    pub fn new(
+        metrics: Option<&Vec<CompilationMetricsMetadata>>,
+        status: CompileStatus,
+        cache_status: Option<&'static str>,
+        rank: Option<u32>,
+    ) -> Self {
+        let first = |f: fn(&CompilationMetricsMetadata) -> Option<f64>| {
+            metrics.and_then(|m| m.iter().find_map(f))
+        };
+        let start_time = first(|m| m.start_time);
+        let compile_time_s = first(|m| m.entire_frame_compile_time_s);
+        let graph_op_count = metrics.and_then(|m| m.iter().find_map(|m| m.graph_op_count));
+        let mut description = Vec::new();
+        if let Some(t) = compile_time_s {
+            description.push(format!("{t:.2}s"));
+        }
+        if let Some(ops) = graph_op_count {
+            description.push(format!("{ops} ops"));
+        }
+        if let Some(cache) = cache_status {
+            description.push(format!("cache {cache}"));
+        }
+        if let Some(rank) = rank {
+            description.push(format!("rank {rank}"));
+        }
+        CompileIdSummary {
+            status: status.css_class(),
+            start_time,
+            compile_time_s,
+            graph_op_count,
+            cache_status,
+            rank,
+            description: description.join(", "),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -958,15 +1643,114 @@ pub struct Diagnostics {
     pub exec_order: Option<ExecOrderSummary>,
 }
 
+/// Context of `multi_rank_index.html`, the landing page of `--all-ranks-html`.  Its fields are
+/// part of the `--template-dir` contract like those of `IndexContext`.
 #[derive(Serialize)]
 pub struct MultiRankContext<'a> {
+    /// `main.css`, or its `--template-dir` override
     pub css: &'a str,
+    /// `--custom-header-html`, as given; empty if not passed
     pub custom_header_html: &'a str,
     pub num_ranks: usize,
+    /// Rank numbers in ascending order; each rank's report is at `rank_<rank>/index.html`
     pub ranks: Vec<String>,
+    /// Script that keeps query parameters on links; render with `format_unescaped`
     pub qps: &'a str,
+    /// Whether the combined `chromium_events.json` has any events
     pub has_chromium_events: bool,
+    /// Whether any divergence across ranks was found, in compile ids or `diagnostics`
     pub show_desync_warning: bool,
+    /// Whether the ranks compiled different compile ids
     pub compile_id_divergence: bool,
+    /// Divergence in cache hits, collectives and tensor metadata across ranks, and the runtime
+    /// analysis; its groups are empty and `analysis` is None when there is nothing to show
     pub diagnostics: Diagnostics,
 }
+
+/// One output file listed under a compile id in `compile_directory.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactRecord {
+    #[serde(default)]
+    pub compile_id: String,
+    pub name: String,
+    pub number: usize,
+    pub url: String,
+    pub hash: Option<String>,
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompileDirectoryEntry {
+    pub artifacts: Vec<ArtifactRecord>,
+}
+
+/// A compilation failure or restart, as listed in failures_and_restarts.html
+#[derive(Debug, Clone, Serialize)]
+pub struct FailureRecord {
+    pub compile_id: String,
+    // "RestartAnalysis" for restarts
+    pub failure_type: String,
+    pub reason: String,
+    pub user_frame: Option<String>,
+}
+
+/// Flat, per-compile-id records of a parse.  `parse_path_summary` collects them as it parses;
+/// `parse_summary` recovers them from the machine readable outputs.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ParseSummary {
+    pub compile_ids: Vec<String>,
+    pub artifacts: Vec<ArtifactRecord>,
+    // compilation_metrics payloads, each with its compile_id added
+    pub metrics: Vec<Value>,
+    pub failures: Vec<FailureRecord>,
+    /// Files of each compile id, as listed in the index; only filled by a parse
+    #[serde(skip)]
+    pub files: FxIndexMap<String, Vec<OutputFile>>,
+    /// Status of each compile id, as colored in the stack trie; only filled by a parse
+    #[serde(skip)]
+    pub statuses: FxHashMap<String, CompileStatus>,
+    /// Stacks the compile ids were compiled from; only filled by a parse
+    #[serde(skip)]
+    pub stack_trie: StackTrieNode,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SingleFileContext {
+    pub title: String,
+    // JSON object of output path to base64'd gzipped content
+    pub files: String,
+    pub js: &'static str,
+}
+
+/// An output `parse_path_deferred` leaves out of its `ParseOutput`, with what to produce it from
+#[derive(Debug, Clone)]
+pub enum DeferredOutput {
+    /// The payload of the envelope on this (1-indexed) line of the log
+    Payload { lineno: usize },
+    /// Syntax highlighted, line anchored view of another output
+    Highlighted {
+        source: PathBuf,
+        language: crate::parsers::CodeLanguage,
+    },
+    /// The log itself, raw.log
+    RawLog,
+}
+
+pub type DeferredOutputs = FxHashMap<PathBuf, DeferredOutput>;
+
+#[derive(Debug, Serialize)]
+pub struct ServeCompileDirContext {
+    pub css: &'static str,
+    pub compile_id: String,
+    // With urls relative to the root of the server
+    pub files: Vec<OutputFile>,
+}
+
+/// An envelope and its payload lines: the (1-indexed) line it starts on, and the bytes of the
+/// log they take up
+#[derive(Debug, Clone)]
+pub struct LogSpan {
+    pub lineno: usize,
+    pub range: std::ops::Range<u64>,
+}
@@ -0,0 +1,69 @@
+// Minimal tlparse parser plugin used by test_plugin_dir.  Built as a cdylib by the test.
+// It claims the "shiny_new_event" envelope key, echoes its input back as a file, and
+// reports how many envelopes it saw from finish.
+use std::ffi::{c_char, CStr, CString};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[repr(C)]
+pub struct TlparsePluginV1 {
+    abi_version: u32,
+    name: *const c_char,
+    metadata_key: *const c_char,
+    parse: extern "C" fn(*const c_char) -> *mut c_char,
+    finish: Option<extern "C" fn() -> *mut c_char>,
+    free: extern "C" fn(*mut c_char),
+}
+
+unsafe impl Sync for TlparsePluginV1 {}
+
+static SEEN: AtomicUsize = AtomicUsize::new(0);
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+extern "C" fn parse(input: *const c_char) -> *mut c_char {
+    SEEN.fetch_add(1, Ordering::SeqCst);
+    let input = unsafe { CStr::from_ptr(input) }.to_str().unwrap();
+    let response = format!(
+        r#"{{"outputs": [{{"file": "echo.json", "content": {}}}]}}"#,
+        json_string(input)
+    );
+    CString::new(response).unwrap().into_raw()
+}
+
+extern "C" fn finish() -> *mut c_char {
+    let response = format!(
+        r#"{{"outputs": [{{"file": "echo_summary.txt", "content": "saw {}"}}]}}"#,
+        SEEN.load(Ordering::SeqCst)
+    );
+    CString::new(response).unwrap().into_raw()
+}
+
+extern "C" fn free(output: *mut c_char) {
+    drop(unsafe { CString::from_raw(output) });
+}
+
+static PLUGIN: TlparsePluginV1 = TlparsePluginV1 {
+    abi_version: 1,
+    name: c"echo".as_ptr(),
+    metadata_key: c"shiny_new_event".as_ptr(),
+    parse,
+    finish: Some(finish),
+    free,
+};
+
+#[no_mangle]
+pub extern "C" fn tlparse_plugin_v1() -> *const TlparsePluginV1 {
+    &PLUGIN
+}
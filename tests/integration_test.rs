@@ -1,6 +1,7 @@
 use assert_cmd::Command;
 use predicates::boolean::PredicateBooleanExt;
 use predicates::str;
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
@@ -365,9 +366,9 @@ fn test_parse_chromium_event() {
 #[test]
 fn test_cache_hit_miss() {
     let expected_files = [
-        "-_1_0_0/fx_graph_cache_miss_33.json",
-        "-_1_0_0/fx_graph_cache_miss_9.json",
-        "-_1_0_0/fx_graph_cache_hit_20.json",
+        "-_1_0_0/fx_graph_cache_miss_38.json",
+        "-_1_0_0/fx_graph_cache_miss_10.json",
+        "-_1_0_0/fx_graph_cache_hit_23.json",
         "compile_directory.json",
         "index.html",
     ];
@@ -1754,6 +1755,72 @@ fn test_all_ranks_basic() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn test_all_ranks_content_addressed() -> Result<(), Box<dyn std::error::Error>> {
+    // Two ranks that dump the same graphs and code
+    let temp_dir = tempdir().unwrap();
+    let input_dir = temp_dir.path().join("logs");
+    fs::create_dir(&input_dir)?;
+    for rank in 0..2 {
+        fs::copy(
+            "tests/inputs/cache_hit_miss.log",
+            input_dir.join(format!("dedicated_log_torch_trace_rank_{rank}.log")),
+        )?;
+    }
+    let out_dir = temp_dir.path().join("out");
+
+    let mut cmd = Command::cargo_bin("tlparse")?;
+    cmd.arg(&input_dir)
+        .arg("--all-ranks-html")
+        .arg("--content-addressed")
+        .arg("-o")
+        .arg(&out_dir)
+        .arg("--no-browser");
+    cmd.assert().success();
+
+    // The ranks share one payloads/ at the top of the output
+    assert!(out_dir.join("payloads").is_dir());
+    assert!(!out_dir.join("rank_0/payloads").exists());
+    let rank0_directory: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        out_dir.join("rank_0/compile_directory.json"),
+    )?)?;
+    let rank1_directory: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        out_dir.join("rank_1/compile_directory.json"),
+    )?)?;
+    let payload_urls = |directory: &serde_json::Value| -> Vec<String> {
+        directory["[0/0]"]["artifacts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|a| a["url"].as_str())
+            .filter(|url| url.starts_with("../payloads/"))
+            .map(String::from)
+            .collect()
+    };
+    let rank0_urls = payload_urls(&rank0_directory);
+    assert!(!rank0_urls.is_empty());
+    for url in &rank0_urls {
+        assert!(out_dir.join("rank_0").join(url).is_file());
+    }
+    // The graphs and code of both ranks are stored once
+    assert_eq!(payload_urls(&rank1_directory), rank0_urls);
+    let config = tlparse::ParseConfig {
+        content_addressed: true,
+        ..Default::default()
+    };
+    let one_rank = tlparse::parse_path(&PathBuf::from("tests/inputs/cache_hit_miss.log"), &config)?;
+    assert_eq!(
+        fs::read_dir(out_dir.join("payloads"))?.count(),
+        one_rank
+            .iter()
+            .filter(|(path, _)| path.starts_with("payloads"))
+            .count()
+    );
+    let rank0_index = fs::read_to_string(out_dir.join("rank_0/index.html"))?;
+    assert!(rank0_index.contains(&format!(r#"href="{}""#, rank0_urls[0])));
+    Ok(())
+}
+
 #[test]
 fn test_all_ranks_messy_input() -> Result<(), Box<dyn std::error::Error>> {
     let input_dir = PathBuf::from("tests/inputs/multi_rank_messy_input");
@@ -2631,3 +2698,1257 @@ fn test_graph_execution_order_diagnostics() -> Result<(), Box<dyn std::error::Er
 
     Ok(())
 }
+
+#[test]
+fn test_guard_diff() {
+    // rank_3 recompiles frame 0 seven times, each time with a different static size for x
+    let path = Path::new("tests/inputs/multi_rank_logs/dedicated_log_torch_trace_rank_3.log")
+        .to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config);
+    assert!(output.is_ok());
+    let map: HashMap<PathBuf, String> = output.unwrap().into_iter().collect();
+
+    let guard_diff = &map[&PathBuf::from("guard_diff.html")];
+    assert!(guard_diff.contains("[0/0] &rarr; [0/1]"));
+    assert!(guard_diff.contains("[0/5] &rarr; [0/6]"));
+    assert!(guard_diff.contains("guard-changed"));
+    assert!(guard_diff.contains("size=[2, 2], stride=[2, 1]"));
+    assert!(guard_diff.contains("size=[3, 3], stride=[3, 1]"));
+    assert!(guard_diff.contains("size mismatch at index 0. expected 2, actual 3"));
+    assert!(map[&PathBuf::from("index.html")].contains("guard_diff.html"));
+
+    let guards = parsers::normalize_cpp_guards(
+        "TREE_GUARD_MANAGER:\n+- RootGuardManager\n| +- DEFAULT_DEVICE: utils_device.CURRENT_DEVICE == None    # _dynamo/output_graph.py:631 in init_ambient_guards\n| +- GuardManager: source=L['x'], accessed_by=FrameLocalsGuardAccessor(key='x', framelocals_idx=0)\n| | +- TENSOR_MATCH: check_tensor(L['x'], Tensor, size=[s0, 2], stride=[2, 1])\n",
+    );
+    assert_eq!(guards.len(), 2);
+    assert_eq!(guards[0].kind, "DEFAULT_DEVICE");
+    assert_eq!(guards[0].code, "utils_device.CURRENT_DEVICE == None");
+    assert_eq!(guards[1].subject(), "TENSOR_MATCH: check_tensor(L['x']");
+}
+
+#[test]
+fn test_tensor_inputs() {
+    let path = Path::new("tests/inputs/cache_hit_miss.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config);
+    assert!(output.is_ok());
+    let map: HashMap<PathBuf, String> = output.unwrap().into_iter().collect();
+
+    let inputs = &map[&PathBuf::from("-_0_0_0/tensor_inputs_2.html")];
+    assert!(inputs.contains("L[&#39;args&#39;][0]"));
+    assert!(inputs.contains("torch.float32"));
+    assert!(inputs.contains("[1, 4, 512, 64]"));
+    assert!(inputs.contains("[131072, 32768, 64, 1]"));
+    // Listed like every other page, with its own number
+    assert!(
+        map[&PathBuf::from("-_0_0_0/compilation_metrics_3.html")].contains("tensor_inputs_2.html")
+    );
+    assert!(
+        map[&PathBuf::from("index.html")].contains(r#"<a href="-_0_0_0/tensor_inputs_2.html">"#)
+    );
+    assert!(map[&PathBuf::from("compile_directory.json")].contains("-_0_0_0/tensor_inputs_2.html"));
+}
+
+#[test]
+fn test_dynamic_shapes_dashboard() {
+    let path = Path::new("tests/inputs/dynamic_shapes.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config);
+    assert!(output.is_ok());
+    let map: HashMap<PathBuf, String> = output.unwrap().into_iter().collect();
+
+    let dashboard = &map[&PathBuf::from("dynamic_shapes.html")];
+    // [0/0] and [0/1] each create their own s0, with their own value, range and stack
+    assert!(dashboard.contains("L[&#39;x&#39;].size()[0]"));
+    assert!(dashboard.contains("<td>8</td>\n        <td>[2, int_oo]</td>"));
+    assert!(dashboard.contains("<td>6</td>\n        <td>[4, int_oo]</td>"));
+    assert!(dashboard.contains("return x + 1"));
+    // and each guard is a use of its own compile's s0 only
+    let rows: Vec<&str> = dashboard.split("<tr").collect();
+    let s0_rows: Vec<&&str> = rows
+        .iter()
+        .filter(|row| row.contains("<code>s0</code>"))
+        .collect();
+    assert_eq!(s0_rows.len(), 2);
+    assert!(s0_rows[0].contains("<td>[0/0]</td>"));
+    assert!(s0_rows[0].contains("guard: <code>Eq(Mod(s0, 2), 0)</code>"));
+    assert!(!s0_rows[0].contains("s0 &gt;= 4"));
+    assert!(s0_rows[1].contains("<td>[0/1]</td>"));
+    assert!(s0_rows[1].contains("guard: <code>s0 &gt;= 4</code>"));
+    assert!(dashboard.contains("(unbacked)"));
+    assert!(dashboard.contains("1 of the symbols below are unbacked"));
+    assert!(dashboard.contains("L[&#39;z&#39;].size()[1]"));
+    assert!(dashboard.contains("Eq(s1, 4)"));
+    assert!(dashboard.contains("Eq(Mod(s0, 2), 0)"));
+    assert!(dashboard.contains("x.view(-1, 2)"));
+    assert!(map[&PathBuf::from("index.html")].contains("dynamic_shapes.html"));
+}
+
+#[test]
+fn test_sym_expr_provenance_outside_export() {
+    let path = Path::new("tests/inputs/export.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: false,
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config);
+    assert!(output.is_ok());
+    let map: HashMap<PathBuf, String> = output.unwrap().into_iter().collect();
+
+    let guard_pages: Vec<&String> = map
+        .iter()
+        .filter(|(p, _)| {
+            p.to_str()
+                .unwrap()
+                .starts_with("-_-_-_-/symbolic_guard_information")
+        })
+        .map(|(_, v)| v)
+        .collect();
+    assert_eq!(guard_pages.len(), 8);
+    // The guard on u0 is traced back through the expressions that produced it
+    assert!(guard_pages
+        .iter()
+        .any(|page| page.contains("Method:</span> int_floordiv") && page.contains(">u0</h3>")));
+}
+
+#[test]
+fn test_cache_report() {
+    let path = Path::new("tests/inputs/cache_hit_miss.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config);
+    assert!(output.is_ok());
+    let map: HashMap<PathBuf, String> = output.unwrap().into_iter().collect();
+
+    let report = &map[&PathBuf::from("cache_report.html")];
+    assert!(report.contains(
+        "<td> fx_graph_cache </td> <td> 1 </td> <td> 2 </td> <td> 0 </td> <td> 33.3% </td>"
+    ));
+    assert!(report.contains("<td> aotautograd_cache </td> <td> 0 </td> <td> 0 </td> <td> 3 </td>"));
+    assert!(report.contains("Unsupported call_function target flex_attention"));
+    // The second miss of frame 1 was caused by a different graph module
+    assert!(report.contains("<td><code>gm</code></td>"));
+    assert!(report.contains("osxu7jvxrxvfgwz7co25iilkcoi7r6wwkfxjkdf7i35nrmqqnas"));
+    assert!(map[&PathBuf::from("index.html")].contains("cache_report.html"));
+
+    let changed = parsers::diff_cache_components(
+        &["[aaa] fx_kwargs[is_backward]: False".to_string()],
+        &["[bbb] fx_kwargs[is_backward]: True".to_string()],
+    );
+    assert_eq!(changed.len(), 1);
+    assert_eq!(changed[0].name, "fx_kwargs[is_backward]");
+    assert_eq!(changed[0].before, "False");
+    assert_eq!(changed[0].after, "True");
+}
+
+#[test]
+fn test_kernel_inventory() {
+    let path = Path::new("tests/inputs/multi_rank_runtime/dedicated_log_torch_trace_rank_0.log")
+        .to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: false,
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config);
+    assert!(output.is_ok());
+    let map: HashMap<PathBuf, String> = output.unwrap().into_iter().collect();
+
+    let kernels = &map[&PathBuf::from("kernels.html")];
+    // [0/1], [0/2] and [0/3] generate the same kernels, which are listed once
+    assert_eq!(
+        kernels
+            .matches("<td><code>triton_per_fused_all_reduce_gelu_native_layer_norm_0</code></td>")
+            .count(),
+        1
+    );
+    assert!(kernels.contains("<td>reduction</td>"));
+    assert!(kernels.contains("[0/1] [0/2] [0/3]"));
+    assert!(kernels.contains("aten.native_layer_norm"));
+    assert!(kernels.contains("[0/0]: miss, 2 configs, 288.6 ms"));
+    assert!(kernels.contains("<td><code>extern_kernels.mm</code></td>"));
+    assert!(map[&PathBuf::from("index.html")].contains("kernels.html"));
+
+    let code =
+        "def call(args):\n    extern_kernels.mm(arg0_1, arg1_1, out=buf0)\n    return (buf0, )\n";
+    let fallback = parsers::extract_kernels(code);
+    assert_eq!(fallback.len(), 1);
+    assert_eq!(fallback[0].kind, "extern");
+    assert!(fallback[0].source.is_none());
+    assert!(parsers::is_fallback_output_code(code, &fallback));
+    // Empty and C++ output code define no Triton kernels either, but aren't fallbacks
+    assert!(!parsers::is_fallback_output_code("", &[]));
+    let cpp = "cpp_fused_mm_0 = async_compile.cpp_pybinding(['const float*'], cpp_source)\ndef call(args):\n    extern_kernels.mm(arg0_1, arg1_1, out=buf0)\n    cpp_fused_mm_0(buf0)\n";
+    assert!(!parsers::is_fallback_output_code(
+        cpp,
+        &parsers::extract_kernels(cpp)
+    ));
+}
+
+#[test]
+fn test_autotuning_report() {
+    let path = Path::new("tests/inputs/autotune_with_compile_id.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config);
+    assert!(output.is_ok());
+    let map: HashMap<PathBuf, String> = output.unwrap().into_iter().collect();
+
+    let report = &map[&PathBuf::from("autotuning.html")];
+    // The benchmarks run while [0/0] runs for the first time, without a compile id of their own
+    assert!(report.contains(r#"<h2 id="[0/0]">[0/0]</h2>"#));
+    assert!(report.contains("Benchmarked 3 time(s), for 31.4 ms."));
+    assert!(report.contains(
+        "<tr> <td><code>triton_poi_fused_mul_0</code></td> <td>  </td> <td>  </td> <td></td> <td> 311.8 </td> </tr>"
+    ));
+    assert!(map[&PathBuf::from("index.html")].contains("autotuning.html"));
+
+    let path = Path::new("tests/inputs/multi_rank_runtime/dedicated_log_torch_trace_rank_0.log")
+        .to_path_buf();
+    let map: HashMap<PathBuf, String> = tlparse::parse_path(&path, &Default::default())
+        .unwrap()
+        .into_iter()
+        .collect();
+    let report = &map[&PathBuf::from("autotuning.html")];
+    assert!(report.contains("<td> only 1 config </td> <td>  </td> <td><code>XBLOCK=1, num_warps=8, num_stages=1</code></td>"));
+}
+
+#[test]
+fn test_template_autotuning() {
+    let path = Path::new("tests/inputs/template_autotuning.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        ..Default::default()
+    };
+    let map: HashMap<PathBuf, String> = tlparse::parse_path(&path, &config)
+        .unwrap()
+        .into_iter()
+        .collect();
+    let report = &map[&PathBuf::from("autotuning.html")];
+    assert!(report.contains("Autotuning ops took 4.000 s."));
+    // Ops are ranked by autotune time, across the run and in each compile
+    let addmm = report.find(
+        "<tr> <td><code>addmm</code></td> <td> 1 </td> <td> 2.500 </td> <td> 62.5% </td> </tr>",
+    );
+    let mm = report
+        .find("<tr> <td><code>mm</code></td> <td> 1 </td> <td> 1.500 </td> <td> 37.5% </td> </tr>");
+    assert!(addmm.unwrap() < mm.unwrap());
+    // The benchmark inside the mm autotuning belongs to [0/0]
+    assert!(report.contains("Benchmarked 1 time(s), for 10.0 ms."));
+    assert!(report.contains(
+        "<tr> <td><code>mm</code></td> <td> 64x128, 128x256 </td> <td> torch.float16, torch.float16 </td> <td> 3 (2 Triton) </td> \
+         <td><code>triton_mm_1</code><br>BLOCK_M=64, BLOCK_N=64, BLOCK_K=32, num_stages=3, num_warps=4</td> <td> 0.0101 </td> <td></td> <td> 1500.0 </td> </tr>"
+    ));
+    assert!(report.contains(
+        "<td><code>extern_addmm</code></td> <td> 0.0087 </td> <td><code>triton_mm_5</code> (0.0094 ms)<br>BLOCK_M=32, BLOCK_N=64, BLOCK_K=32, num_stages=2, num_warps=4</td> <td> 2500.0 </td>"
+    ));
+}
+
+#[test]
+fn test_compilation_metrics_runtime_and_feature_usage() {
+    let path = Path::new("tests/inputs/autotune_with_compile_id.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config);
+    assert!(output.is_ok());
+    let map: HashMap<PathBuf, String> = output.unwrap().into_iter().collect();
+
+    let runtime = map
+        .iter()
+        .find(|(k, _)| k.to_str().unwrap().contains("compilation_metrics_runtime"))
+        .map(|(_, v)| v)
+        .expect("compilation_metrics_runtime page not found");
+    assert!(runtime.contains("Cudagraphify time (us)"));
+    // Runtime metrics are also attached to the compile's metrics page
+    let metrics = map
+        .iter()
+        .find(|(k, _)| {
+            k.to_str()
+                .unwrap()
+                .starts_with("-_0_0_0/compilation_metrics_")
+        })
+        .map(|(_, v)| v)
+        .expect("compilation_metrics page not found");
+    assert!(metrics.contains("Runtime Metrics"));
+    assert!(metrics.contains("282272"));
+
+    let features = &map[&PathBuf::from("feature_usage.html")];
+    assert!(features.contains("fx_cache"));
+    assert!(map[&PathBuf::from("index.html")].contains("feature_usage.html"));
+}
+
+#[test]
+fn test_unknown_envelope_fallback() {
+    let path = Path::new("tests/inputs/unknown_envelope.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config);
+    assert!(output.is_ok());
+    let map: HashMap<PathBuf, String> = output.unwrap().into_iter().collect();
+
+    let with_payload = &map[&PathBuf::from("-_0_0_0/unknown_shiny_new_event_0.json")];
+    let json: serde_json::Value = serde_json::from_str(with_payload).unwrap();
+    assert_eq!(json["metadata"]["name"], "foo");
+    assert_eq!(json["payload"]["nodes"][1], 2);
+    let without_payload = &map[&PathBuf::from("-_0_0_0/unknown_shiny_new_event_1.json")];
+    assert!(without_payload.contains("\"bar\""));
+    let text = &map[&PathBuf::from("-_0_0_0/unknown_weird_text_dump_2.txt")];
+    assert!(text.ends_with("line one\nline two"));
+
+    let index = &map[&PathBuf::from("index.html")];
+    assert!(index.contains("unknown_shiny_new_event_0.json"));
+    assert!(index.contains("<li><code>shiny_new_event</code> (2 occurrences)</li>"));
+}
+
+#[test]
+fn test_custom_parser_claims_unknown_envelope() {
+    use tlparse::parsers::{
+        CompileId, Envelope, Metadata, ParserOutput, ParserResults, StructuredLogParser,
+    };
+
+    struct ShinyParser;
+    impl StructuredLogParser for ShinyParser {
+        fn name(&self) -> &'static str {
+            "shiny_new_event"
+        }
+        fn get_metadata<'e>(&self, e: &'e Envelope) -> Option<Metadata<'e>> {
+            e.other("shiny_new_event")
+        }
+        fn parse<'e>(
+            &self,
+            _lineno: usize,
+            metadata: Metadata<'e>,
+            _rank: Option<u32>,
+            _compile_id: &Option<CompileId>,
+            _payload: &str,
+        ) -> anyhow::Result<ParserResults> {
+            let Metadata::Other(key, value) = metadata else {
+                anyhow::bail!("unexpected metadata");
+            };
+            Ok(vec![ParserOutput::File(
+                PathBuf::from(format!("{key}.txt")),
+                value["name"].as_str().unwrap_or_default().to_string(),
+            )])
+        }
+    }
+
+    let path = Path::new("tests/inputs/unknown_envelope.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        custom_parsers: vec![Box::new(ShinyParser)],
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config);
+    assert!(output.is_ok());
+    let map: HashMap<PathBuf, String> = output.unwrap().into_iter().collect();
+
+    assert_eq!(map[&PathBuf::from("shiny_new_event_0.txt")], "foo");
+    assert_eq!(map[&PathBuf::from("shiny_new_event_1.txt")], "bar");
+    // Claimed keys are no longer handed to the fallback renderer
+    assert!(!map
+        .keys()
+        .any(|k| k.to_string_lossy().contains("unknown_shiny_new_event")));
+    assert!(map
+        .keys()
+        .any(|k| k.to_string_lossy().contains("unknown_weird_text_dump")));
+}
+
+#[test]
+fn test_custom_parser_finish_hook() {
+    use tlparse::parsers::{
+        CompileId, Envelope, FinishContext, Metadata, ParserOutput, ParserResults,
+        StructuredLogParser,
+    };
+
+    #[derive(Default)]
+    struct MetricsCounter {
+        seen: RefCell<Vec<String>>,
+    }
+    impl StructuredLogParser for MetricsCounter {
+        fn name(&self) -> &'static str {
+            "metrics_counter"
+        }
+        fn get_metadata<'e>(&self, e: &'e Envelope) -> Option<Metadata<'e>> {
+            e.compilation_metrics
+                .as_ref()
+                .map(Metadata::CompilationMetrics)
+        }
+        fn parse<'e>(
+            &self,
+            _lineno: usize,
+            _metadata: Metadata<'e>,
+            _rank: Option<u32>,
+            compile_id: &Option<CompileId>,
+            _payload: &str,
+        ) -> anyhow::Result<ParserResults> {
+            self.seen
+                .borrow_mut()
+                .push(compile_id.as_ref().unwrap().to_string());
+            Ok(Vec::new())
+        }
+        fn finish(&self, ctx: &FinishContext) -> anyhow::Result<ParserResults> {
+            let seen = self.seen.take();
+            Ok(vec![
+                ParserOutput::File(
+                    PathBuf::from("metrics_counter.txt"),
+                    format!(
+                        "{} metrics across {} compile ids: {}",
+                        seen.len(),
+                        ctx.directory.len(),
+                        seen.join(", ")
+                    ),
+                ),
+                ParserOutput::Link("dashboard".to_string(), "https://example.com".to_string()),
+            ])
+        }
+    }
+
+    let path = Path::new("tests/inputs/comp_metrics.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        custom_parsers: vec![Box::new(MetricsCounter::default())],
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config);
+    assert!(output.is_ok());
+    let map: HashMap<PathBuf, String> = output.unwrap().into_iter().collect();
+
+    let summary = &map[&PathBuf::from("metrics_counter.txt")];
+    assert_eq!(
+        summary,
+        "3 metrics across 5 compile ids: [0/0_1], [1/0_1], [2/0]"
+    );
+    let index = &map[&PathBuf::from("index.html")];
+    assert!(index.contains(r#"<li><a href="metrics_counter.txt">metrics_counter.txt</a></li>"#));
+    assert!(index.contains(r#"<li><a href="https://example.com">dashboard</a></li>"#));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_plugin_dir() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir().unwrap();
+    let plugin_dir = temp_dir.path().join("plugins");
+    let out_dir = temp_dir.path().join("out");
+    fs::create_dir(&plugin_dir)?;
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let status = std::process::Command::new(rustc)
+        .args(["--edition", "2021", "--crate-type", "cdylib", "-o"])
+        .arg(plugin_dir.join("libecho_plugin.so"))
+        .arg("tests/inputs/plugins/echo_plugin.rs")
+        .status()?;
+    assert!(status.success());
+
+    let mut cmd = Command::cargo_bin("tlparse")?;
+    cmd.arg("tests/inputs/unknown_envelope.log")
+        .arg("--plugin-dir")
+        .arg(&plugin_dir)
+        .arg("--overwrite")
+        .arg("-o")
+        .arg(&out_dir)
+        .arg("--no-browser");
+    cmd.assert().success();
+
+    let echo: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(out_dir.join("-_0_0_0/echo_0.json"))?)?;
+    assert_eq!(echo["key"], "shiny_new_event");
+    assert_eq!(echo["metadata"]["name"], "foo");
+    assert_eq!(echo["compile_id"]["frame_id"], 0);
+    assert_eq!(echo["payload"], r#"{"graph": "g", "nodes": [1, 2]}"#);
+    assert_eq!(
+        fs::read_to_string(out_dir.join("echo_summary.txt"))?,
+        "saw 2"
+    );
+    // The plugin claimed the key, so only the other one falls back
+    assert!(!out_dir
+        .join("-_0_0_0/unknown_shiny_new_event_0.json")
+        .exists());
+    let index = fs::read_to_string(out_dir.join("index.html"))?;
+    assert!(index.contains(r#"<a href="echo_summary.txt">"#));
+    assert!(index.contains("unknown_weird_text_dump"));
+    Ok(())
+}
+
+#[test]
+fn test_render_rules_config() {
+    let rules = tlparse::config::TlparseConfig::load(Path::new("tests/inputs/render_rules.toml"))
+        .unwrap()
+        .rules;
+    let path = Path::new("tests/inputs/render_rules.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        render_rules: rules,
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config);
+    assert!(output.is_ok());
+    let map: HashMap<PathBuf, String> = output.unwrap().into_iter().collect();
+
+    let table = &map[&PathBuf::from("-_0_0_0/pass_stats_0.html")];
+    assert!(table.contains("<th>pass</th>"));
+    assert!(table.contains("<td>&lt;removed&gt;</td>"));
+    assert!(!map.contains_key(&PathBuf::from("-_0_0_0/pass_stats_0.json")));
+    let code = &map[&PathBuf::from("-_0_0_0/codegen_prologue_1.html")];
+    assert!(code.contains("<pre"));
+    let notes = &map[&PathBuf::from("-_0_0_0/team_notes_4.html")];
+    assert!(notes.contains("<em>emphasis</em>"));
+    assert!(notes.contains("&lt;script&gt;alert"));
+    assert!(!notes.contains("<script>alert"));
+    assert!(notes.contains(r#"<a href="https://pytorch.org/docs">docs</a>"#));
+    assert!(notes.contains(r#"<a href="../index.html">index</a>"#));
+    assert!(notes.contains(r##"<a href="#notes">top</a>"##));
+    assert!(notes.contains(r##"<a href="#">click</a>"##));
+    assert!(notes.contains(r##"<a href="#">sneaky</a>"##));
+    assert!(notes.contains(r##"<img src="#" alt="pixel" />"##));
+    assert!(!notes.to_lowercase().contains("javascript:"));
+    assert!(!notes.contains("data:image"));
+
+    let index = &map[&PathBuf::from("index.html")];
+    assert!(index.contains(
+        r#"<li class="group">Passes</li><li class="grouped"><a href="-_0_0_0/pass_stats_0.html">Pass statistics</a>"#
+    ));
+    // Hidden artifacts are still written, just not listed
+    assert!(map.contains_key(&PathBuf::from("-_0_0_0/noisy_debug_1_2.txt")));
+    assert!(!index.contains("noisy_debug_1_2.txt"));
+    assert!(index.contains("other_debug_3.txt"));
+}
+
+#[test]
+fn test_skip_and_only_parsers() {
+    let path = Path::new("tests/inputs/simple.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        skip_parsers: vec!["inductor_output_code".to_string()],
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config);
+    assert!(output.is_ok());
+    let map: HashMap<PathBuf, String> = output.unwrap().into_iter().collect();
+    // Skipped envelopes fall back to their raw payload
+    let raw = map
+        .iter()
+        .find(|(k, _)| {
+            let k = k.to_string_lossy();
+            k.starts_with("-_0_0_0/inductor_output_code") && k.ends_with(".txt")
+        })
+        .map(|(_, v)| v)
+        .expect("raw inductor_output_code payload not found");
+    assert!(!raw.contains("<html"));
+    assert!(!map.keys().any(|k| k
+        .to_string_lossy()
+        .starts_with("-_0_0_0/inductor_output_code")
+        && k.to_string_lossy().ends_with(".html")));
+
+    let config = tlparse::ParseConfig {
+        strict: true,
+        only_parsers: vec!["dynamo_output_graph".to_string()],
+        ..Default::default()
+    };
+    let map: HashMap<PathBuf, String> = tlparse::parse_path(&path, &config)
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert!(prefix_exists(&map, "-_0_0_0/dynamo_output_graph"));
+    // Metadata-only envelopes of skipped parsers are written out as their metadata
+    let metrics = map
+        .iter()
+        .find(|(k, _)| {
+            k.to_string_lossy()
+                .starts_with("-_0_0_0/compilation_metrics")
+        })
+        .map(|(k, v)| (k.to_string_lossy().to_string(), v))
+        .expect("compilation_metrics metadata not found");
+    assert!(metrics.0.ends_with(".json"));
+    assert!(metrics.1.contains("\"frame_key\""));
+    assert!(prefix_exists(&map, "-_0_0_0/aot_inference_graph"));
+    assert!(prefix_exists(&map, "-_0_0_0/fx_graph_cache_miss"));
+
+    let parsers = tlparse::registered_parsers(&tlparse::ParseConfig::default());
+    assert!(parsers.contains(&("link_parser", vec!["link".to_string()])));
+    assert!(parsers
+        .iter()
+        .any(|(name, _)| *name == "compilation_metrics"));
+    assert!(parsers.iter().any(|(name, _)| *name == "autotuning"));
+
+    // Run-wide reports are skipped like parsers
+    let path = Path::new("tests/inputs/autotune_with_compile_id.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        skip_parsers: vec!["autotuning".to_string(), "feature_usage".to_string()],
+        ..Default::default()
+    };
+    let map: HashMap<PathBuf, String> = tlparse::parse_path(&path, &config)
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert!(!map.contains_key(&PathBuf::from("autotuning.html")));
+    assert!(!map.contains_key(&PathBuf::from("feature_usage.html")));
+    assert!(!map[&PathBuf::from("index.html")].contains("autotuning.html"));
+    // The metrics page is still rendered by compilation_metrics
+    assert!(prefix_exists(&map, "-_0_0_0/compilation_metrics_"));
+    assert!(!map.keys().any(|k| {
+        let k = k.to_string_lossy();
+        k.contains("compilation_metrics_") && k.ends_with(".json")
+    }));
+
+    // Skipping tensor_inputs alone leaves compilation_metrics envelopes to their parser
+    let path = Path::new("tests/inputs/comp_metrics.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        skip_parsers: vec!["tensor_inputs".to_string()],
+        ..Default::default()
+    };
+    let map: HashMap<PathBuf, String> = tlparse::parse_path(&path, &config)
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert!(!map.keys().any(|k| {
+        let k = k.to_string_lossy();
+        k.contains("tensor_inputs") || k.ends_with(".json") && k.contains("compilation_metrics_")
+    }));
+
+    // Export reports evaluated guards as failures unless guard_added is skipped
+    let path = Path::new("tests/inputs/export_guard_added.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        export: true,
+        skip_parsers: vec!["guard_added".to_string()],
+        ..Default::default()
+    };
+    let map: HashMap<PathBuf, String> = tlparse::parse_path(&path, &config)
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert!(!prefix_exists(&map, "-_-_-_-/symbolic_guard_information"));
+    assert!(prefix_exists(&map, "-_-_-_-/guard_added"));
+    assert!(!map[&PathBuf::from("index.html")].contains("Guard Evaluated"));
+}
+
+#[test]
+fn test_parsers_subcommand() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlparse")?;
+    cmd.arg("parsers");
+    cmd.assert()
+        .success()
+        .stdout(str::contains("inductor_output_code: inductor_output_code"))
+        .stdout(str::contains(
+            "guard_added: guard_added, propagate_real_tensors_provenance",
+        ));
+    Ok(())
+}
+
+#[test]
+fn test_parser_error_kinds() {
+    use tlparse::parsers::{
+        CompileId, Envelope, Metadata, ParserError, ParserResults, StructuredLogParser,
+    };
+
+    struct FailingParser;
+    impl StructuredLogParser for FailingParser {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+        fn get_metadata<'e>(&self, e: &'e Envelope) -> Option<Metadata<'e>> {
+            e.other("shiny_new_event")
+        }
+        fn parse<'e>(
+            &self,
+            _lineno: usize,
+            _metadata: Metadata<'e>,
+            _rank: Option<u32>,
+            _compile_id: &Option<CompileId>,
+            payload: &str,
+        ) -> anyhow::Result<ParserResults> {
+            if payload.is_empty() {
+                return Err(ParserError::Custom {
+                    kind: "missing_payload".to_string(),
+                    message: "shiny_new_event needs a payload".to_string(),
+                }
+                .into());
+            }
+            // Not a list, so this fails to decode
+            let _: Vec<u32> = serde_json::from_str(payload)?;
+            Ok(Vec::new())
+        }
+    }
+
+    let path = Path::new("tests/inputs/unknown_envelope.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        custom_parsers: vec![Box::new(FailingParser)],
+        ..Default::default()
+    };
+    let map: HashMap<PathBuf, String> = tlparse::parse_path(&path, &config)
+        .unwrap()
+        .into_iter()
+        .collect();
+    let index = &map[&PathBuf::from("index.html")];
+    assert!(index.contains("<tr> <td>failing</td> <td>payload_decode</td> <td>1</td> </tr>"));
+    assert!(index.contains("<tr> <td>failing</td> <td>missing_payload</td> <td>1</td> </tr>"));
+
+    let strict = tlparse::ParseConfig {
+        strict: true,
+        custom_parsers: vec![Box::new(FailingParser)],
+        ..Default::default()
+    };
+    assert!(tlparse::parse_path(&path, &strict).is_err());
+}
+
+#[test]
+fn test_dynamo_guards_failure_stats() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("bad_guards.log");
+    fs::write(
+        &path,
+        "V0403 07:28:48.064000 139877824898048 torch/_dynamo/guards.py:1894] \
+         {\"dynamo_guards\": {}, \"frame_id\": 0, \"frame_compile_id\": 0, \"attempt\": 0, \
+         \"has_payload\": \"83e12cc6068a0f3c5555be0d55fb01bb\"}\n\tnot json\n",
+    )
+    .unwrap();
+    let output = tlparse::parse_path(&path, &tlparse::ParseConfig::default()).unwrap();
+    let stats: serde_json::Value = serde_json::from_str(
+        &output
+            .iter()
+            .find(|(p, _)| p == Path::new("parse_stats.json"))
+            .unwrap()
+            .1,
+    )
+    .unwrap();
+    assert_eq!(stats["fail_dynamo_guards_json"], 1);
+    assert_eq!(stats["fail_parser"], 0);
+    assert_eq!(
+        stats["parser_errors"],
+        serde_json::json!([["dynamo_guards", "payload_decode", 1]])
+    );
+}
+
+#[test]
+fn test_parse_summary() {
+    let path = Path::new("tests/inputs/comp_failure.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        strict: true,
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config).unwrap();
+    let summary = tlparse::parse_summary(&output).unwrap();
+    assert_eq!(summary.compile_ids, vec!["[0/0]".to_string()]);
+
+    let graph = summary
+        .artifacts
+        .iter()
+        .find(|a| a.name == "dynamo_output_graph_0.txt")
+        .unwrap();
+    assert_eq!(graph.compile_id, "[0/0]");
+    assert!(graph
+        .content
+        .as_ref()
+        .unwrap()
+        .contains("class GraphModule"));
+
+    assert_eq!(summary.metrics.len(), 1);
+    assert_eq!(summary.metrics[0]["compile_id"], "[0/0]");
+    assert_eq!(summary.failures.len(), 1);
+    assert_eq!(summary.failures[0].failure_type, "BackendCompilerFailed");
+    assert!(summary.failures[0].reason.contains("broken backend"));
+
+    // Collected while parsing, the records are the same
+    let (_, parsed) = tlparse::parse_path_summary(&path, &config).unwrap();
+    assert_eq!(
+        serde_json::to_value(&parsed).unwrap(),
+        serde_json::to_value(&summary).unwrap()
+    );
+    assert_eq!(parsed.statuses["[0/0]"], tlparse::CompileStatus::Error);
+    assert_eq!(parsed.files["[0/0]"].len(), summary.artifacts.len());
+    assert!(!parsed.stack_trie.is_empty());
+}
+
+#[test]
+fn test_highlighted_code_views() {
+    let path = Path::new("tests/inputs/inductor_provenance_aot_log.txt").to_path_buf();
+    let map: HashMap<PathBuf, String> = tlparse::parse_path(&path, &Default::default())
+        .unwrap()
+        .into_iter()
+        .collect();
+    // The raw code stays as is, and the highlighted view doesn't take up an output number
+    assert!(map.contains_key(&PathBuf::from("-_-_-_-/inductor_aot_wrapper_code_8.txt")));
+    assert!(map.contains_key(&PathBuf::from("-_-_-_-/compilation_metrics_13.html")));
+    let cpp = &map[&PathBuf::from("-_-_-_-/inductor_aot_wrapper_code_8.html")];
+    assert!(cpp.contains(r#"<span id="L3"><span style="font-style:italic;color:#969896;">// Definition of AOTI runtime interface functions</span></span>"#));
+    let index = &map[&PathBuf::from("index.html")];
+    assert!(
+        index.contains(r#"(<a href="-_-_-_-/inductor_aot_wrapper_code_8.html">readable_html</a>)"#)
+    );
+    let fx = &map[&PathBuf::from("-_-_-_-/fx_graph_runnable_4.html")];
+    assert!(fx.contains(r#"<span id="L1">"#));
+    assert!(index.contains(r#"(<a href="-_-_-_-/fx_graph_runnable_4.html">readable_html</a>)"#));
+
+    let plain_text = tlparse::ParseConfig {
+        plain_text: true,
+        ..Default::default()
+    };
+    let map: HashMap<PathBuf, String> = tlparse::parse_path(&path, &plain_text)
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert!(map.contains_key(&PathBuf::from("-_-_-_-/fx_graph_runnable_4.txt")));
+    assert!(!map.contains_key(&PathBuf::from("-_-_-_-/fx_graph_runnable_4.html")));
+
+    // The C++ guard tree is highlighted as C++
+    let path = Path::new("tests/inputs/links.log").to_path_buf();
+    let map: HashMap<PathBuf, String> = tlparse::parse_path(&path, &Default::default())
+        .unwrap()
+        .into_iter()
+        .collect();
+    let guards = &map[&PathBuf::from("-_0_0_0/dynamo_cpp_guards_str_6.html")];
+    assert!(
+        guards.contains(r#"<span id="L2"><span style="color:#323232;">TREE_GUARD_MANAGER</span>"#)
+    );
+}
+
+#[test]
+fn test_single_file() -> Result<(), Box<dyn std::error::Error>> {
+    use base64::Engine;
+    use std::io::Read;
+
+    let temp_dir = tempdir().unwrap();
+    let out = temp_dir.path().join("report");
+    let mut cmd = Command::cargo_bin("tlparse")?;
+    cmd.arg("tests/inputs/comp_metrics.log")
+        .arg("--single-file")
+        .arg("-o")
+        .arg(&out)
+        .arg("--no-browser");
+    cmd.assert().success();
+    assert!(!out.exists());
+
+    let html = fs::read_to_string(temp_dir.path().join("report.html"))?;
+    let files_json = html
+        .split(r#"<script id="tlparse-files" type="application/json">"#)
+        .nth(1)
+        .and_then(|rest| rest.split("</script>").next())
+        .unwrap();
+    let files: HashMap<String, String> = serde_json::from_str(files_json)?;
+    assert!(files.contains_key("-_0_0_1/dynamo_output_graph_0.txt"));
+
+    let gzipped = base64::engine::general_purpose::STANDARD.decode(&files["index.html"])?;
+    let mut index = String::new();
+    flate2::read::GzDecoder::new(&gzipped[..]).read_to_string(&mut index)?;
+    assert!(index.contains(r#"<a href="-_0_0_1/dynamo_output_graph_0.txt">"#));
+    Ok(())
+}
+
+#[test]
+fn test_content_addressed() {
+    let path = Path::new("tests/inputs/cache_hit_miss.log").to_path_buf();
+    let config = tlparse::ParseConfig {
+        content_addressed: true,
+        ..Default::default()
+    };
+    let output = tlparse::parse_path(&path, &config).unwrap();
+    let map: HashMap<PathBuf, String> = output.iter().cloned().collect();
+    // Every file is written once
+    assert_eq!(map.len(), output.len());
+    assert!(!map.contains_key(&PathBuf::from("-_0_0_0/dynamo_output_graph_0.txt")));
+
+    let directory: serde_json::Value =
+        serde_json::from_str(&map[&PathBuf::from("compile_directory.json")]).unwrap();
+    let graphs: Vec<&serde_json::Value> = directory["[0/0]"]["artifacts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|a| {
+            a["name"] == "dynamo_output_graph_0.txt" || a["name"] == "dynamo_output_graph_15.txt"
+        })
+        .collect();
+    assert_eq!(graphs.len(), 2);
+    let hash = graphs[0]["hash"].as_str().unwrap();
+    assert_eq!(graphs[1]["hash"], hash);
+    // Outputs that aren't payload dumps are hashed too
+    let metrics = directory["[0/0]"]["artifacts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|a| {
+            a["name"]
+                .as_str()
+                .unwrap()
+                .starts_with("compilation_metrics")
+        })
+        .unwrap();
+    assert!(metrics["hash"].is_string());
+    let url = format!("payloads/{hash}.txt");
+    assert_eq!(graphs[0]["url"], url.as_str());
+    assert_eq!(graphs[1]["url"], url.as_str());
+    assert!(map[&PathBuf::from(&url)].contains("class GraphModule"));
+    assert!(map[&PathBuf::from("raw.jsonl")].contains(&format!(r#""payload_filename":"{url}""#)));
+
+    // Hashing every output is only worth it when content addressing
+    let map: HashMap<PathBuf, String> = tlparse::parse_path(&path, &Default::default())
+        .unwrap()
+        .into_iter()
+        .collect();
+    let directory: serde_json::Value =
+        serde_json::from_str(&map[&PathBuf::from("compile_directory.json")]).unwrap();
+    assert!(directory["[0/0]"]["artifacts"][0]["hash"].is_null());
+}
+
+#[test]
+fn test_serve_lazy_report() -> anyhow::Result<()> {
+    use std::io::{Read, Write};
+
+    let path = Path::new("tests/inputs/comp_metrics.log").to_path_buf();
+    let config = tlparse::ParseConfig::default();
+    let output = tlparse::parse_path(&path, &config)?;
+    let map: HashMap<PathBuf, String> = output.iter().cloned().collect();
+    let (_, deferred) = tlparse::parse_path_deferred(&path, &config)?;
+    assert!(deferred.contains_key(Path::new("raw.log")));
+    assert!(deferred.contains_key(Path::new("-_0_0_1/dynamo_output_graph_0.txt")));
+
+    // Opening the log only indexes it
+    let mut report = tlparse::serve::LazyReport::open(&path, &config)?;
+    assert_eq!(report.paths().count(), 0);
+
+    // The first request for a compile id's file parses the log up to the end of the compile id,
+    // which names its files as the full report does
+    let listing = report.get(Path::new("-_0_0_1/index.html"))?.unwrap();
+    assert!(listing.contains(r#"<a href="/-_0_0_1/dynamo_output_graph_0.txt">"#));
+    assert!(!listing.contains("-_1_0_1"));
+    assert_eq!(
+        report.get(Path::new("-_0_0_1/dynamo_output_graph_0.txt"))?,
+        Some(map[Path::new("-_0_0_1/dynamo_output_graph_0.txt")].as_str())
+    );
+    assert!(report.paths().all(|p| !p.starts_with("-_1_0_1")));
+    let listing = report.get(Path::new("-_1_0_1/index.html"))?.unwrap();
+    assert!(listing.contains(r#"<a href="/-_1_0_1/dynamo_output_graph_3.txt">"#));
+    assert!(!listing.contains("dynamo_output_graph_0.txt"));
+    for file in ["dynamo_output_graph_3.txt", "compilation_metrics_5.html"] {
+        let file = Path::new("-_1_0_1").join(file);
+        assert_eq!(report.get(&file)?, Some(map[&file].as_str()));
+    }
+    assert_eq!(
+        report.get(Path::new("-_1_0_1/dynamo_output_graph_0.txt"))?,
+        None
+    );
+    assert!(report
+        .paths()
+        .all(|p| p != Path::new("failures_and_restarts.html")));
+
+    // Pages about the whole run render the full report, which then serves every page
+    assert_eq!(
+        report.get(Path::new("failures_and_restarts.html"))?,
+        Some(map[Path::new("failures_and_restarts.html")].as_str())
+    );
+    assert_eq!(report.paths().count(), output.len());
+    for (file, content) in &output {
+        assert_eq!(
+            report.get(file)?,
+            Some(content.as_str()),
+            "{}",
+            file.display()
+        );
+    }
+
+    // Provenance tracking pages are rendered with their compile id too
+    let path = Path::new("tests/inputs/inductor_provenance_jit_log.txt").to_path_buf();
+    let provenance_config = tlparse::ParseConfig {
+        inductor_provenance: true,
+        ..Default::default()
+    };
+    let provenance_output: HashMap<PathBuf, String> =
+        tlparse::parse_path(&path, &provenance_config)?
+            .into_iter()
+            .collect();
+    let (_, deferred) = tlparse::parse_path_deferred(&path, &provenance_config)?;
+    assert!(deferred.keys().any(|p| p.starts_with("-_0_0_0")));
+    let mut report = tlparse::serve::LazyReport::open(&path, &provenance_config)?;
+    let page = Path::new("provenance_tracking_-_0_0_0.html");
+    // The compile id ends with the log, so this renders the full report
+    assert_eq!(report.get(page)?, Some(provenance_output[page].as_str()));
+    assert!(report.paths().any(|p| p == Path::new("kernels.html")));
+
+    // The config isn't Send, so the server answers on this thread
+    let report =
+        tlparse::serve::LazyReport::open(Path::new("tests/inputs/comp_metrics.log"), &config)?;
+    let mut server = tlparse::serve::Server::bind(report, 0)?;
+    let addr = server.url()["http://".len()..]
+        .trim_end_matches('/')
+        .to_string();
+    let client = std::thread::spawn(move || -> anyhow::Result<Vec<String>> {
+        let get = |url: &str| -> anyhow::Result<String> {
+            let mut stream = std::net::TcpStream::connect(&addr)?;
+            write!(stream, "GET {url} HTTP/1.0\r\n\r\n")?;
+            let mut response = String::new();
+            stream.read_to_string(&mut response)?;
+            Ok(response)
+        };
+        [
+            "/-_1_0_1/",
+            "/-_1_0_1/dynamo_output_graph_3.txt",
+            "/?query=1",
+            "/-_1_0_1/",
+            "/-_2_0_0/dynamo_output_graph_6.txt",
+            "/missing.html",
+        ]
+        .into_iter()
+        .map(get)
+        .collect()
+    });
+    for _ in 0..6 {
+        server.answer_next()?;
+    }
+    let responses = client.join().unwrap()?;
+    assert!(responses[0].contains(r#"<a href="/-_1_0_1/dynamo_output_graph_3.txt">"#));
+    assert!(responses[1].ends_with(&map[Path::new("-_1_0_1/dynamo_output_graph_3.txt")]));
+    // The index is the full report's
+    assert!(responses[2].starts_with("HTTP/1.0 200"));
+    assert!(responses[2].ends_with(&map[Path::new("index.html")]));
+    // Its links are the ones the compile id was parsed with
+    assert_eq!(responses[3], responses[0]);
+    assert!(responses[4].ends_with(&map[Path::new("-_2_0_0/dynamo_output_graph_6.txt")]));
+    assert!(responses[5].starts_with("HTTP/1.0 404"));
+    Ok(())
+}
+
+#[test]
+fn test_search_index() {
+    let path = Path::new("tests/inputs/comp_failure.log").to_path_buf();
+    let output = tlparse::parse_path(&path, &Default::default()).unwrap();
+    let map: HashMap<PathBuf, String> = output.into_iter().collect();
+    assert!(map[Path::new("index.html")].contains(r#"<script src="search_index.js"></script>"#));
+    let index: serde_json::Value = serde_json::from_str(
+        map[Path::new("search_index.js")]
+            .strip_prefix("window.tlparseSearchIndex = ")
+            .unwrap()
+            .trim_end()
+            .trim_end_matches(';'),
+    )
+    .unwrap();
+    let docs = index["docs"].as_array().unwrap();
+
+    // Graphs link to the line a token first appears on in their highlighted view
+    let postings = index["tokens"]["GraphModule"].as_array().unwrap();
+    let doc = &docs[postings[0].as_u64().unwrap() as usize];
+    assert_eq!(doc["compile_id"], "[0/0]");
+    assert_eq!(doc["kind"], "dynamo_output_graph");
+    assert_eq!(doc["url"], "-_0_0_0/dynamo_output_graph_0.html");
+    assert_eq!(doc["anchored"], true);
+    assert_eq!(postings[1], 1);
+
+    // Failure reasons are searchable too
+    let failure = docs.iter().position(|d| d["kind"] == "failure").unwrap();
+    assert_eq!(docs[failure]["url"], "failures_and_restarts.html");
+    assert!(index["tokens"]
+        .as_object()
+        .unwrap()
+        .values()
+        .any(|postings| {
+            postings
+                .as_array()
+                .unwrap()
+                .chunks(2)
+                .any(|posting| posting[0] == failure)
+        }));
+
+    // C++ guard trees are line anchored too
+    let path = Path::new("tests/inputs/links.log").to_path_buf();
+    let output = tlparse::parse_path(&path, &Default::default()).unwrap();
+    let map: HashMap<PathBuf, String> = output.into_iter().collect();
+    let index: serde_json::Value = serde_json::from_str(
+        map[Path::new("search_index.js")]
+            .strip_prefix("window.tlparseSearchIndex = ")
+            .unwrap()
+            .trim_end()
+            .trim_end_matches(';'),
+    )
+    .unwrap();
+    let docs = index["docs"].as_array().unwrap();
+    let postings = index["tokens"]["RootGuardManager"].as_array().unwrap();
+    let doc = &docs[postings[0].as_u64().unwrap() as usize];
+    assert_eq!(doc["kind"], "dynamo_cpp_guards_str");
+    assert_eq!(doc["url"], "-_0_0_0/dynamo_cpp_guards_str_6.html");
+    assert_eq!(doc["anchored"], true);
+    assert_eq!(postings[1], 3);
+    let page = &map[Path::new("-_0_0_0/dynamo_cpp_guards_str_6.html")];
+    assert!(page.contains(r#"<span id="L3"><span style="font-weight:bold;color:#a71d5d;">+-</span><span style="color:#323232;"> RootGuardManager</span></span>"#));
+}
+
+#[test]
+fn test_template_dir() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("main.css"), "body \\{ color: teal; }").unwrap();
+    fs::write(
+        dir.path().join("index.html"),
+        "<style>{css}</style><p>Custom index with {num_breaks} breaks</p>",
+    )
+    .unwrap();
+    let config = tlparse::ParseConfig {
+        templates: tlparse::config::Templates::load(dir.path()).unwrap(),
+        ..Default::default()
+    };
+    assert!(config.templates.is_overridden("main.css"));
+    assert!(!config.templates.is_overridden("failures.css"));
+    let path = Path::new("tests/inputs/simple.log").to_path_buf();
+    let output = tlparse::parse_path(&path, &config).unwrap();
+    let map: HashMap<PathBuf, String> = output.into_iter().collect();
+    let index = &map[&PathBuf::from("index.html")];
+    assert!(index.contains("Custom index with 0 breaks"));
+    assert!(index.contains("color: teal"));
+    let metrics = map
+        .iter()
+        .find(|(path, _)| path.to_string_lossy().contains("compilation_metrics_"))
+        .unwrap()
+        .1;
+    assert!(metrics.contains("color: teal"));
+
+    fs::write(dir.path().join("indx.html"), "").unwrap();
+    let err = tlparse::config::Templates::load(dir.path()).unwrap_err();
+    assert!(format!("{err:#}").contains("indx.html"));
+}
+
+#[test]
+fn test_markdown_summary() {
+    let path = Path::new("tests/inputs/comp_metrics.log").to_path_buf();
+    let output = tlparse::parse_path(&path, &tlparse::ParseConfig::default()).unwrap();
+    let stats: serde_json::Value = serde_json::from_str(
+        &output
+            .iter()
+            .find(|(p, _)| p == Path::new("parse_stats.json"))
+            .unwrap()
+            .1,
+    )
+    .unwrap();
+    assert_eq!(stats["ok"], 12);
+    let summary = tlparse::markdown_summary(&output, "comp_metrics.log").unwrap();
+    assert!(summary.starts_with("# tlparse summary: comp_metrics.log\n"));
+    assert!(summary.contains("**5 compile ids** (4 restarted, 1 ok)"));
+    assert!(summary.contains(
+        "| [2/0] | torch_dynamo_resume_in_fn_at_9553 (test_misc.py:9553) | ok | 0.00 |  |"
+    ));
+    assert!(summary.contains("## Slowest compiles"));
+    assert!(summary.contains("| [0/0_1] | RestartAnalysis | 'skip function graph_break"));
+    assert!(summary.contains("## Parse stats\n\nok: 12"));
+
+    let path = Path::new("tests/inputs/cache_hit_miss.log").to_path_buf();
+    let output = tlparse::parse_path(&path, &tlparse::ParseConfig::default()).unwrap();
+    let summary = tlparse::markdown_summary(&output, "cache_hit_miss.log").unwrap();
+    assert!(summary.contains("| fx_graph | 1 | 2 | 0 | 33.3% |"));
+    assert!(summary.contains("fx_graph miss/hit, aotautograd bypass"));
+
+    let mut cmd = Command::cargo_bin("tlparse").unwrap();
+    cmd.args(["summary", "tests/inputs/dynamic_shapes.log"])
+        .assert()
+        .success()
+        .stdout(str::contains("| [0] | 2 | [0/0] [0/1] |"));
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_tui() {
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent};
+    use ratatui::Terminal;
+
+    fn screen(terminal: &mut Terminal<TestBackend>, app: &mut tlparse::tui::App) -> String {
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let width = buffer.area.width as usize;
+        let symbols: Vec<&str> = buffer.content.iter().map(|cell| cell.symbol()).collect();
+        symbols
+            .chunks(width)
+            .map(|row| row.concat())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    let press = |app: &mut tlparse::tui::App, code: KeyCode| app.handle_key(KeyEvent::from(code));
+
+    let path = Path::new("tests/inputs/comp_failure.log");
+    let config = tlparse::ParseConfig::default();
+    let report = tlparse::serve::LazyReport::open(path, &config).unwrap();
+    let mut app = tlparse::tui::App::new(report, "comp_failure.log").unwrap();
+    let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+
+    let text = screen(&mut terminal, &mut app);
+    assert!(text.contains("tlparse: comp_failure.log"));
+    assert!(text.contains("Stack trie"));
+    // The failing compile id is on the stack of its frame
+    assert!(text.contains("[0/0] /data/users/xmfan/a/pytorch/test/dynamo/test_misc.py:10755 in fn"));
+
+    press(&mut app, KeyCode::Char('f'));
+    press(&mut app, KeyCode::Char('2'));
+    let text = screen(&mut terminal, &mut app);
+    assert!(text.contains("error"));
+    assert!(text.contains("BackendCompilerFailed"));
+    assert!(text.contains("dynamo_output_graph_0.txt"));
+
+    // Open the first artifact in the pager
+    press(&mut app, KeyCode::Enter);
+    press(&mut app, KeyCode::Enter);
+    let text = screen(&mut terminal, &mut app);
+    assert!(text.contains("-_0_0_0/dynamo_output_graph_0.txt"));
+    assert!(text.contains("class GraphModule"));
+    press(&mut app, KeyCode::Char('q'));
+    let text = screen(&mut terminal, &mut app);
+    assert!(text.contains("Artifacts"));
+
+    // Pages show the envelope they were rendered from
+    press(&mut app, KeyCode::Char('G'));
+    press(&mut app, KeyCode::Enter);
+    let text = screen(&mut terminal, &mut app);
+    assert!(text.contains("-_0_0_0/compilation_metrics_"));
+    assert!(text.contains(r#""fail_type": "BackendCompilerFailed""#));
+}
+
+#[test]
+fn test_index_compile_id_metadata() {
+    let path = Path::new("tests/inputs/comp_metrics.log").to_path_buf();
+    let output = tlparse::parse_path(&path, &tlparse::ParseConfig::default()).unwrap();
+    let map: HashMap<PathBuf, String> = output.into_iter().collect();
+    let index = &map[&PathBuf::from("index.html")];
+    assert!(index.contains(r#"<select id="compile-id-filter">"#));
+    assert!(index.contains(r#"<select id="compile-id-sort">"#));
+    assert!(index.contains(r#"<ul id="compile-ids">"#));
+    // Metrics belong to the attempt that finished; the attempt before it was restarted
+    assert!(index.contains(
+        r#"<li class="compile-id" data-status="status-break" data-start-time="" data-compile-time="" data-op-count="" data-cache="" data-rank="">"#
+    ));
+    assert!(index.contains(
+        r#"<a id="[0/0]" class="status-break">[0/0]</a>
+"#
+    ));
+    assert!(index.contains(
+        r#"<li class="compile-id" data-status="status-ok" data-start-time="1712154528.0523684" data-compile-time="0.012439489364624023" data-op-count="1" data-cache="" data-rank="">"#
+    ));
+    assert!(index.contains(
+        r#"<a id="[0/0_1]" class="status-ok">[0/0_1]</a> <span class="compile-id-summary">0.01s, 1 ops</span>"#
+    ));
+    assert_eq!(
+        index
+            .matches(r#"data-compile-time="0.012439489364624023""#)
+            .count(),
+        1
+    );
+    assert!(index.contains(r#"<a id="[2/0]" class="status-ok">[2/0]</a>"#));
+
+    let path = Path::new("tests/inputs/cache_hit_miss.log").to_path_buf();
+    let output = tlparse::parse_path(&path, &tlparse::ParseConfig::default()).unwrap();
+    let map: HashMap<PathBuf, String> = output.into_iter().collect();
+    let index = &map[&PathBuf::from("index.html")];
+    assert!(index.contains(r#"data-op-count="2" data-cache="miss""#));
+    assert!(index.contains(r#"<span class="compile-id-summary">2 ops, cache miss</span>"#));
+}
@@ -0,0 +1,20 @@
+"""Smoke tests for the Python bindings; run with `maturin develop && pytest tests/python`."""
+
+import os
+
+import pytest
+import tlparse
+
+INPUTS = os.path.join(os.path.dirname(__file__), os.pardir, "inputs")
+
+
+def test_parse():
+    result = tlparse.parse(os.path.join(INPUTS, "comp_metrics.log"))
+    assert set(result) >= {"compile_ids", "artifacts", "metrics", "failures", "files"}
+    assert "index.html" in result["files"]
+    assert [m["compile_id"] for m in result["metrics"]] == ["[0/0_1]", "[1/0_1]", "[2/0]"]
+
+
+def test_parse_error():
+    with pytest.raises(RuntimeError):
+        tlparse.parse(os.path.join(INPUTS, "does_not_exist.log"))